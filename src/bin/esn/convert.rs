@@ -0,0 +1,165 @@
+//! Conversion between e-notation and JSON.
+//!
+//! JSON has fewer kinds of values, so the conversion is lossy: every container becomes an
+//! array, the tail of a dotted list its last element and a hash table an array of
+//! `[key, value]` pairs, every number a JSON number, characters and identifiers become
+//! strings, and quoting and labels become tagged arrays like `["quote", x]` and
+//! `["label", 0, x]`. The other way, arrays become vectors and `null` becomes `()`.
+use enotation::{
+    container::{list::List, Container},
+    labeling::Labeling,
+    literal::{Float, Identifier, Literal, String_},
+    parse_file,
+    quoting::Quoting,
+    syntaxing::Syntaxing,
+    EFile, ENotation, ENotationBody,
+};
+use serde_json::{Map, Number, Value};
+
+/// One pretty JSON value per notation.
+pub fn esn_to_json(file: &EFile) -> String {
+    let mut out = String::new();
+    for notation in &file.notations {
+        let json =
+            serde_json::to_string_pretty(&to_json(notation)).expect("JSON values always serialize");
+        out.push_str(&json);
+        out.push('\n');
+    }
+    out
+}
+
+fn float(value: f64) -> Value {
+    Number::from_f64(value).map_or(Value::Null, Value::Number)
+}
+
+fn bytes(bytes: &[u8]) -> Value {
+    Value::Array(bytes.iter().map(|&b| Value::Number(b.into())).collect())
+}
+
+fn to_json(notation: &ENotation) -> Value {
+    match &notation.body {
+        ENotationBody::Literal(literal) => match literal {
+            Literal::Boolean(b) => Value::Bool(b.value),
+            Literal::Int(i) => i
+                .as_i64()
+                .map_or_else(|| float(i.to_f64()), |i| Value::Number(i.into())),
+            Literal::Rational(r) => float(r.to_f64()),
+            Literal::Float(f) => float(f.value),
+            Literal::Char(c) => Value::String(c.value.to_string()),
+            Literal::String_(s) => Value::String(s.value.clone()),
+            Literal::Identifier(i) => Value::String(i.symbol().to_string()),
+            Literal::Keyword(k) => Value::String(k.to_string()),
+            Literal::ByteString(b) => bytes(&b.value),
+            Literal::Complex(c) => Value::String(c.to_string()),
+        },
+        ENotationBody::Container(container) => match container {
+            Container::List(List::PL(l)) if l.elems.is_empty() => Value::Null,
+            Container::List(l) => {
+                Value::Array(l.elems().iter().chain(l.tail()).map(to_json).collect())
+            }
+            Container::Vector(v) => Value::Array(v.elems().iter().map(to_json).collect()),
+            Container::Set(s) => Value::Array(s.elems.iter().map(to_json).collect()),
+            Container::Object(o) => Value::Object(
+                o.pairs
+                    .iter()
+                    .map(|pair| (pair.key.symbol().to_string(), to_json(&pair.value)))
+                    .collect::<Map<_, _>>(),
+            ),
+            Container::Bytevector(b) => bytes(&b.bytes),
+            Container::Hash(h) => Value::Array(
+                h.entries
+                    .iter()
+                    .map(|entry| Value::Array(vec![to_json(&entry.key), to_json(&entry.value)]))
+                    .collect(),
+            ),
+        },
+        ENotationBody::Quoting(q) => {
+            let (tag, value) = match q {
+                Quoting::Quote(q) => ("quote", &q.value),
+                Quoting::QuasiQuote(q) => ("quasiquote", &q.value),
+                Quoting::Unquote(q) => ("unquote", &q.value),
+                Quoting::UnquoteSplicing(q) => ("unquote-splicing", &q.value),
+            };
+            Value::Array(vec![Value::String(tag.to_string()), to_json(value)])
+        }
+        ENotationBody::Syntaxing(s) => {
+            let (tag, value) = match s {
+                Syntaxing::Syntax(s) => ("syntax", &s.value),
+                Syntaxing::QuasiSyntax(s) => ("quasisyntax", &s.value),
+                Syntaxing::Unsyntax(s) => ("unsyntax", &s.value),
+                Syntaxing::UnsyntaxSplicing(s) => ("unsyntax-splicing", &s.value),
+            };
+            Value::Array(vec![Value::String(tag.to_string()), to_json(value)])
+        }
+        ENotationBody::Labeling(Labeling::Label(l)) => Value::Array(vec![
+            Value::String("label".to_string()),
+            Value::Number(l.label.into()),
+            to_json(&l.value),
+        ]),
+        ENotationBody::Labeling(Labeling::LabelRef(r)) => Value::Array(vec![
+            Value::String("label-ref".to_string()),
+            Value::Number(r.label.into()),
+        ]),
+        ENotationBody::Error(_) => Value::Null,
+    }
+}
+
+/// Every JSON value of `source` as a formatted notation.
+pub fn json_to_esn(source: &str) -> Result<String, String> {
+    let mut text = String::new();
+    for value in serde_json::Deserializer::from_str(source).into_iter::<Value>() {
+        let value = value.map_err(|err| format!("invalid JSON: {}", err))?;
+        write_esn(&value, &mut text)?;
+        text.push('\n');
+    }
+    let file = parse_file(&text).map_err(|err| format!("cannot convert: {}", err))?;
+    Ok(file.pretty(80))
+}
+
+fn key(name: &str) -> Result<String, String> {
+    Identifier::from_symbol(name)
+        .map(|key| key.name)
+        .ok_or_else(|| format!("`{}` cannot be an object key", name))
+}
+
+fn write_esn(value: &Value, out: &mut String) -> Result<(), String> {
+    match value {
+        Value::Null => out.push_str("()"),
+        Value::Bool(b) => out.push_str(if *b { "#t" } else { "#f" }),
+        Value::Number(n) => match (n.as_i64(), n.as_u64()) {
+            (Some(i), _) => out.push_str(&i.to_string()),
+            (_, Some(u)) => out.push_str(&u.to_string()),
+            _ => out.push_str(
+                &Float {
+                    value: n.as_f64().unwrap_or(f64::NAN),
+                    exactness: None,
+                }
+                .to_string(),
+            ),
+        },
+        Value::String(s) => out.push_str(&String_ { value: s.clone() }.to_string()),
+        Value::Array(elems) => {
+            out.push_str("#(");
+            for (i, elem) in elems.iter().enumerate() {
+                if i > 0 {
+                    out.push(' ');
+                }
+                write_esn(elem, out)?;
+            }
+            out.push(')');
+        }
+        Value::Object(pairs) => {
+            out.push('{');
+            for (i, (name, value)) in pairs.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                out.push_str(&key(name)?);
+                out.push_str(" : ");
+                write_esn(value, out)?;
+            }
+            out.push('}');
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,208 @@
+//! `esn`, checks, formats and converts e-notation files.
+use std::{
+    fs,
+    io::{self, Read, Write},
+    process::ExitCode,
+};
+
+use enotation::{cst, parse_file, parse_file_recovering, pretty::Style, ParseError};
+
+mod convert;
+#[cfg(test)]
+mod tests;
+
+const USAGE: &str = "usage: esn <command> [options] [files...]
+
+Reads standard input when no file or `-` is given.
+
+commands:
+  check                   report every error, exits with 1 if any file is broken
+  fmt [-w] [--width N]    print formatted files, or rewrite them in place with -w
+  parse                   dump the syntax tree
+  convert --from F --to F convert between `esn` and `json`";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Esn,
+    Json,
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Check,
+    Fmt { width: usize, write: bool },
+    Parse,
+    Convert { from: Format, to: Format },
+}
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    command: Command,
+    files: Vec<String>,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut args = args.into_iter();
+    let command = args.next().ok_or("missing command")?;
+    let mut files = vec![];
+    let (mut width, mut write, mut from, mut to) = (80, false, None, None);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("missing value for {}", name));
+        match arg.as_str() {
+            "-w" | "--write" => write = true,
+            "--width" => {
+                width = value("--width")?
+                    .parse()
+                    .map_err(|_| "--width expects a number")?
+            }
+            "--from" => from = Some(parse_format(&value("--from")?)?),
+            "--to" => to = Some(parse_format(&value("--to")?)?),
+            "-" => files.push(arg),
+            flag if flag.starts_with('-') => return Err(format!("unknown option `{}`", flag)),
+            _ => files.push(arg),
+        }
+    }
+    let command = match command.as_str() {
+        "check" => Command::Check,
+        "fmt" => Command::Fmt { width, write },
+        "parse" => Command::Parse,
+        "convert" => Command::Convert {
+            from: from.ok_or("convert needs --from")?,
+            to: to.ok_or("convert needs --to")?,
+        },
+        other => return Err(format!("unknown command `{}`", other)),
+    };
+    if files.is_empty() {
+        files.push("-".to_string());
+    }
+    Ok(Args { command, files })
+}
+
+fn parse_format(name: &str) -> Result<Format, String> {
+    match name {
+        "esn" => Ok(Format::Esn),
+        "json" => Ok(Format::Json),
+        other => Err(format!(
+            "unknown format `{}`, expected `esn` or `json`",
+            other
+        )),
+    }
+}
+
+fn read_input(path: &str) -> io::Result<String> {
+    if path == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path)
+    }
+}
+
+fn display_name(path: &str) -> String {
+    if path == "-" {
+        "<stdin>".to_string()
+    } else {
+        path.to_string()
+    }
+}
+
+/// Renders `errors` of the file at `path` to stderr.
+fn report(path: &str, source: &str, errors: Vec<ParseError>) {
+    for mut err in errors {
+        err.span.file = Some(display_name(path));
+        if let Some(related) = &mut err.related {
+            related.file = err.span.file.clone();
+        }
+        let _ = err.write_to(source, io::stderr());
+    }
+}
+
+/// Runs `command` on one file, returns whether it succeeded.
+fn run(command: &Command, path: &str, out: &mut impl Write) -> Result<bool, String> {
+    let source = read_input(path).map_err(|err| format!("{}: {}", display_name(path), err))?;
+    let io_error = |err: io::Error| err.to_string();
+    match command {
+        Command::Check => {
+            let (_, errors) = parse_file_recovering(&source);
+            let ok = errors.is_empty();
+            report(path, &source, errors);
+            Ok(ok)
+        }
+        Command::Fmt { width, write } => {
+            // the lossless tree keeps the comments the AST drops
+            let root = match cst::parse(&source) {
+                Ok(root) => root,
+                Err(err) => {
+                    report(path, &source, vec![err]);
+                    return Ok(false);
+                }
+            };
+            let formatted = Style::default().with_width(*width).print_tree(&root);
+            if *write && path != "-" {
+                fs::write(path, formatted).map_err(io_error)?;
+            } else {
+                out.write_all(formatted.as_bytes()).map_err(io_error)?;
+            }
+            Ok(true)
+        }
+        Command::Parse => match parse_file(&source) {
+            Ok(file) => {
+                writeln!(out, "{:#?}", file.notations).map_err(io_error)?;
+                Ok(true)
+            }
+            Err(err) => {
+                report(path, &source, vec![err]);
+                Ok(false)
+            }
+        },
+        Command::Convert { from, to } => {
+            let converted = match (from, to) {
+                (Format::Esn, Format::Json) => match parse_file(&source) {
+                    Ok(file) => convert::esn_to_json(&file),
+                    Err(err) => {
+                        report(path, &source, vec![err]);
+                        return Ok(false);
+                    }
+                },
+                (Format::Json, Format::Esn) => convert::json_to_esn(&source)?,
+                (Format::Esn, Format::Esn) => match parse_file(&source) {
+                    Ok(file) => file.to_string(),
+                    Err(err) => {
+                        report(path, &source, vec![err]);
+                        return Ok(false);
+                    }
+                },
+                (Format::Json, Format::Json) => source,
+            };
+            out.write_all(converted.as_bytes()).map_err(io_error)?;
+            Ok(true)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("esn: {}\n\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    let mut ok = true;
+    let mut stdout = io::stdout().lock();
+    for path in &args.files {
+        match run(&args.command, path, &mut stdout) {
+            Ok(file_ok) => ok &= file_ok,
+            Err(err) => {
+                eprintln!("esn: {}", err);
+                ok = false;
+            }
+        }
+    }
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,89 @@
+use insta::assert_snapshot;
+
+use super::{convert, parse_args, run, Args, Command, Format};
+
+fn args(args: &str) -> Result<Args, String> {
+    parse_args(args.split_whitespace().map(String::from))
+}
+
+#[test]
+fn parse_command_line() {
+    assert_eq!(
+        args("fmt -w --width 60 a.esn b.esn").unwrap(),
+        Args {
+            command: Command::Fmt {
+                width: 60,
+                write: true
+            },
+            files: vec!["a.esn".to_string(), "b.esn".to_string()],
+        }
+    );
+    assert_eq!(
+        args("convert --from json --to esn").unwrap(),
+        Args {
+            command: Command::Convert {
+                from: Format::Json,
+                to: Format::Esn
+            },
+            files: vec!["-".to_string()],
+        }
+    );
+    assert_snapshot!(args("lint").unwrap_err(), @"unknown command `lint`");
+    assert_snapshot!(args("convert --from yaml --to esn").unwrap_err(), @"unknown format `yaml`, expected `esn` or `json`");
+    assert_snapshot!(args("check --quiet").unwrap_err(), @"unknown option `--quiet`");
+}
+
+#[test]
+fn esn_to_json() {
+    let file = enotation::parse_file(
+        "{name : \"esn\", port : 80, ratio : 1/2, tags : [a |b c|], none : ()} '(#t #\\x)",
+    )
+    .unwrap();
+    assert_snapshot!(convert::esn_to_json(&file), @r#"
+    {
+      "name": "esn",
+      "none": null,
+      "port": 80,
+      "ratio": 0.5,
+      "tags": [
+        "a",
+        "b c"
+      ]
+    }
+    [
+      "quote",
+      [
+        true,
+        "x"
+      ]
+    ]
+    "#);
+}
+
+#[test]
+fn json_to_esn() {
+    let esn = convert::json_to_esn(
+        r#"{"name": "esn", "port": 80, "ratio": 0.5, "tags": ["a", null, true], "a key": 1.0}"#,
+    )
+    .unwrap();
+    assert_snapshot!(esn, @r#"{|a key| : 1.0, name : "esn", port : 80, ratio : 0.5, tags : #("a" () #t)}"#);
+}
+
+#[test]
+fn fmt_keeps_comments() {
+    let dir = std::env::temp_dir().join(format!("esn-fmt-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("commented.esn");
+    std::fs::write(&path, "; keep me\n(a   b) #| and me |#").unwrap();
+    let command = Command::Fmt {
+        width: 80,
+        write: false,
+    };
+    let mut out = vec![];
+    assert!(run(&command, path.to_str().unwrap(), &mut out).unwrap());
+    assert_snapshot!(String::from_utf8(out).unwrap(), @r"
+    ; keep me
+    (a b) #| and me |#
+    ");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
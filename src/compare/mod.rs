@@ -0,0 +1,205 @@
+//! Structural equality, hashing and ordering of notations.
+//!
+//! Spans are ignored, two notations are equal when they read the same, so:
+//!
+//! - `(a b)` equals `[a b]`, and `#(a b)` equals `#[a b]`, brackets are only a style.
+//! - `|abc|` equals `abc`, identifiers compare by [`symbol`](Identifier::symbol).
+//! - Numbers compare by value and exactness, the radix they are written in does not matter,
+//!   so `#x10` equals `16` while `#i16` does not. Different kinds of literals are never equal,
+//!   `4/2` is a rational and not the integer `2`.
+//! - Floats compare with [`f64::total_cmp`], so a NaN equals itself and `-0.0` is not `0.0`.
+//! - A dotted tail compares as written, `(a . (b))` is not `(a b)`, compare their
+//!   [`Value`](crate::Value)s to read them as the same list.
+//! - Every error node of the recovering parser equals every other one.
+//!
+//! Notations of different kinds order by kind, literals before containers before quoting
+//! before syntaxing, in the order of the variants of their enums.
+//!
+//! Sets compare their elements in the order they are written, compare them as sets after
+//! [`Set::canonicalize`](crate::container::set::Set::canonicalize). Hash tables likewise
+//! compare their entries in order, look keys up with
+//! [`HashTable::get`](crate::container::hash::HashTable::get).
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+use num::{bigint::Sign, BigInt};
+
+use crate::{
+    container::{list::List, vector::Vector},
+    literal::{ComplexValue, Float, Identifier, Integer, IntegerValue, Rational},
+    ENotation,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A float ordered by [`f64::total_cmp`].
+struct TotalF64(f64);
+
+impl PartialEq for TotalF64 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TotalF64 {}
+
+impl PartialOrd for TotalF64 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TotalF64 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Hash for TotalF64 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state)
+    }
+}
+
+/// The part of a value equality, hashing and ordering look at.
+trait Key {
+    fn key(&self) -> impl Ord + Hash + '_;
+}
+
+/// Implements `PartialEq`, `Eq`, `Hash`, `PartialOrd` and `Ord` through [`Key`].
+macro_rules! compare_by_key {
+    ($($ty:ty),*) => {$(
+        impl PartialEq for $ty {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+
+        impl Eq for $ty {}
+
+        impl Hash for $ty {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.key().hash(state)
+            }
+        }
+
+        impl PartialOrd for $ty {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $ty {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.key().cmp(&other.key())
+            }
+        }
+    )*};
+}
+
+compare_by_key!(
+    ENotation,
+    Identifier,
+    IntegerValue,
+    Integer,
+    Rational,
+    Float,
+    ComplexValue,
+    List,
+    Vector
+);
+
+impl Key for ENotation {
+    fn key(&self) -> impl Ord + Hash + '_ {
+        &self.body
+    }
+}
+
+impl Key for Identifier {
+    fn key(&self) -> impl Ord + Hash + '_ {
+        self.symbol()
+    }
+}
+
+/// An integer borrowed from an [`IntegerValue`], a `Big` that fits into `i64` is `Small`, so
+/// it still equals its `Small` and every `Big` is beyond every `Small`.
+#[derive(PartialEq, Eq, Hash)]
+enum IntegerKey<'a> {
+    Small(i64),
+    Big(&'a BigInt),
+}
+
+impl PartialOrd for IntegerKey<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for IntegerKey<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let beyond = |big: &BigInt| match big.sign() {
+            Sign::Minus => Ordering::Less,
+            _ => Ordering::Greater,
+        };
+        match (self, other) {
+            (IntegerKey::Small(a), IntegerKey::Small(b)) => a.cmp(b),
+            (IntegerKey::Big(a), IntegerKey::Big(b)) => a.cmp(b),
+            (IntegerKey::Big(a), IntegerKey::Small(_)) => beyond(a),
+            (IntegerKey::Small(_), IntegerKey::Big(b)) => beyond(b).reverse(),
+        }
+    }
+}
+
+impl Key for IntegerValue {
+    fn key(&self) -> impl Ord + Hash + '_ {
+        match self {
+            IntegerValue::Small(value) => IntegerKey::Small(*value),
+            IntegerValue::Big(value) => match i64::try_from(value) {
+                Ok(value) => IntegerKey::Small(value),
+                Err(_) => IntegerKey::Big(value),
+            },
+        }
+    }
+}
+
+impl Key for Integer {
+    fn key(&self) -> impl Ord + Hash + '_ {
+        (&self.value, self.exactness)
+    }
+}
+
+impl Key for Rational {
+    fn key(&self) -> impl Ord + Hash + '_ {
+        (&self.value, self.exactness)
+    }
+}
+
+impl Key for Float {
+    fn key(&self) -> impl Ord + Hash + '_ {
+        (TotalF64(self.value), self.exactness)
+    }
+}
+
+impl Key for ComplexValue {
+    fn key(&self) -> impl Ord + Hash + '_ {
+        match self {
+            ComplexValue::Rectangular(c) => (0, &c.re, &c.im),
+            ComplexValue::Polar { magnitude, angle } => (1, magnitude, angle),
+        }
+    }
+}
+
+impl Key for List {
+    fn key(&self) -> impl Ord + Hash + '_ {
+        (self.elems(), self.tail())
+    }
+}
+
+impl Key for Vector {
+    fn key(&self) -> impl Ord + Hash + '_ {
+        self.elems()
+    }
+}
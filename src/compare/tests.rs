@@ -0,0 +1,103 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use insta::assert_snapshot;
+
+use crate::{
+    container::Container, literal::IntegerValue, parse_notation, ENotation, ENotationBody,
+};
+
+fn notation(input: &str) -> ENotation {
+    parse_notation(input).unwrap()
+}
+
+fn hash(notation: &ENotation) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    notation.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn equal_ignores_spans_and_style() {
+    for (a, b) in [
+        ("(a b)", "[a  b]"),
+        ("#(1 (2))", "#[1 [2]]"),
+        ("|abc|", "abc"),
+        ("#x10", "16"),
+        ("#b-1/10", "-1/2"),
+        ("+nan.0", "+nan.0"),
+        ("{k : '(x)}", "{k :\n '[x]}"),
+        ("#`(#,a)", "#`[#,a]"),
+    ] {
+        assert_eq!(notation(a), notation(b), "{} = {}", a, b);
+        assert_eq!(hash(&notation(a)), hash(&notation(b)), "{} = {}", a, b);
+    }
+}
+
+#[test]
+fn not_equal() {
+    for (a, b) in [
+        ("1", "1.0"),
+        ("2", "4/2"),
+        ("16", "#i16"),
+        ("0.0", "-0.0"),
+        ("\"a\"", "a"),
+        ("(a b)", "#(a b)"),
+        ("#{a b}", "#{b a}"),
+    ] {
+        assert_ne!(notation(a), notation(b), "{} != {}", a, b);
+    }
+}
+
+#[test]
+fn total_order() {
+    let mut notations: Vec<_> = [
+        "b", "\"s\"", "#f", "(b)", "-inf.0", "#\\a", "+nan.0", "2", "#t", "1/2", "#{}", "a", "'a",
+        "-1", "(a c)", "#'a", "1.5",
+    ]
+    .into_iter()
+    .map(notation)
+    .collect();
+    notations.sort();
+    let sorted: Vec<_> = notations.iter().map(|n| n.to_string()).collect();
+    assert_snapshot!(sorted.join(" "), @r#"#f #t #\a -inf.0 1.5 +nan.0 1/2 -1 2 "s" a b (a c) (b) #{} 'a #'a"#);
+}
+
+#[test]
+fn big_integers() {
+    let small = IntegerValue::Small(-5);
+    let big = IntegerValue::Big((-5).into());
+    assert_eq!(small, big);
+    let (mut a, mut b) = (DefaultHasher::new(), DefaultHasher::new());
+    small.hash(&mut a);
+    big.hash(&mut b);
+    assert_eq!(a.finish(), b.finish());
+    let mut notations: Vec<_> = [
+        "99999999999999999999",
+        "-1",
+        "-99999999999999999999",
+        "9223372036854775807",
+        "-9223372036854775809",
+    ]
+    .into_iter()
+    .map(notation)
+    .collect();
+    notations.sort();
+    let sorted: Vec<_> = notations.iter().map(|n| n.to_string()).collect();
+    assert_snapshot!(sorted.join(" "), @"-99999999999999999999 -9223372036854775809 -1 9223372036854775807 99999999999999999999");
+}
+
+#[test]
+fn canonical_set() {
+    let mut a = notation("#{c (b) a c}");
+    let mut b = notation("#{[b] a c}");
+    for n in [&mut a, &mut b] {
+        if let ENotationBody::Container(Container::Set(set)) = &mut n.body {
+            set.canonicalize();
+        }
+    }
+    assert_eq!(a, b);
+    assert_snapshot!(a, @"#{a c (b)}");
+}
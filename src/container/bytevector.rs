@@ -0,0 +1,58 @@
+use std::fmt::Display;
+
+use pest::iterators::Pair;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    convert::{from_pest, malformed},
+    literal::parse_byte,
+    ParseErrorKind, Rule,
+};
+
+/// `#u8(...)`, a vector of bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Bytevector {
+    pub bytes: Vec<u8>,
+}
+
+/// The byte an element of a bytevector stands for, an exact integer from 0 to 255.
+fn byte(elem: &Pair<'_, Rule>) -> Result<u8, ParseErrorKind> {
+    let mut pair = elem.clone();
+    while matches!(
+        pair.as_rule(),
+        Rule::notation | Rule::notation_ | Rule::literal
+    ) {
+        match pair.clone().into_inner().next() {
+            Some(inner) => pair = inner,
+            None => break,
+        }
+    }
+    match pair.as_rule() {
+        Rule::int => parse_byte(pair.as_span()),
+        _ => None,
+    }
+    .ok_or_else(|| ParseErrorKind::InvalidByte(elem.as_str().to_string()))
+}
+
+from_pest! {
+    Bytevector = Rule::bytevector => |_, inner| {
+        let bytes = inner
+            .map(|elem| byte(&elem).map_err(|kind| malformed(kind, elem.as_span())))
+            .collect::<Result<_, _>>()?;
+        Ok(Bytevector { bytes })
+    };
+}
+
+impl Display for Bytevector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#u8(")?;
+        for (i, b) in self.bytes.iter().enumerate() {
+            if i == 0 {
+                write!(f, "{}", b)?;
+            } else {
+                write!(f, " {}", b)?;
+            }
+        }
+        write!(f, ")")
+    }
+}
@@ -0,0 +1,198 @@
+use std::fmt::Display;
+
+use from_pest::ConversionError;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    list::{BList, List, PList},
+    Container,
+};
+use crate::{
+    convert::{from_pest, malformed},
+    literal::{Exactness, Literal},
+    visit::{self, Visit},
+    ENotation, ENotationBody, ParseError, ParseErrorKind, Rule,
+};
+
+/// How a hash table compares its keys, named after the Racket procedures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum HashFlavor {
+    /// `#hash`, keys are the same when they are equal notations, as `==` compares them.
+    Equal,
+    /// `#hasheqv`, only literals other than strings and byte strings can be the same key, so
+    /// two containers are different keys even when they are written alike.
+    Eqv,
+    /// `#hasheq`, like `#hasheqv`, but among the numbers only exact integers can be the same
+    /// key.
+    Eq,
+}
+
+impl HashFlavor {
+    /// The opener of a table of this flavor, e.g. `#hash(`.
+    pub fn opener(self) -> &'static str {
+        match self {
+            HashFlavor::Equal => "#hash(",
+            HashFlavor::Eqv => "#hasheqv(",
+            HashFlavor::Eq => "#hasheq(",
+        }
+    }
+
+    pub(crate) fn of_opener(opener: &str) -> Self {
+        match opener {
+            "#hasheqv(" => HashFlavor::Eqv,
+            "#hasheq(" => HashFlavor::Eq,
+            _ => HashFlavor::Equal,
+        }
+    }
+
+    /// Whether `a` and `b` are the same key of a table of this flavor.
+    pub fn same_key(self, a: &ENotation, b: &ENotation) -> bool {
+        let comparable = match &a.body {
+            _ if self == HashFlavor::Equal => true,
+            ENotationBody::Literal(Literal::String_(_) | Literal::ByteString(_)) => false,
+            ENotationBody::Literal(Literal::Int(i)) if self == HashFlavor::Eq => {
+                i.exactness != Some(Exactness::Inexact)
+            }
+            ENotationBody::Literal(
+                Literal::Rational(_) | Literal::Float(_) | Literal::Complex(_),
+            ) => self == HashFlavor::Eqv,
+            ENotationBody::Literal(_) => true,
+            _ => false,
+        };
+        comparable && a == b
+    }
+}
+
+/// A `(key . value)` entry of a hash table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct HashEntry {
+    pub key: ENotation,
+    pub value: ENotation,
+}
+
+impl HashEntry {
+    /// The entry a `(key . value)` pair stands for, `None` for any other notation.
+    pub(crate) fn of_pair(pair: ENotation) -> Option<Self> {
+        let (mut elems, tail) = match pair.body {
+            ENotationBody::Container(Container::List(
+                List::PL(PList { elems, tail }) | List::BL(BList { elems, tail }),
+            )) => (elems, tail?),
+            _ => return None,
+        };
+        match (elems.pop(), elems.is_empty()) {
+            (Some(key), true) => Some(HashEntry { key, value: *tail }),
+            _ => None,
+        }
+    }
+}
+
+/// `#hash((key . value) ...)`, unlike an object its keys can be any notation.
+///
+/// An entry itself cannot be labeled: the table keeps its key and value but not the pair
+/// they were written in, so `#hash(#0=(a . 1))` is rejected as there is nothing for `#0#` to
+/// refer to. The key and the value can be labeled, as in `#hash((#0=a . #0#))`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct HashTable {
+    pub flavor: HashFlavor,
+    /// In the order they are written, `parse_file` rejects a key that is given twice.
+    pub entries: Vec<HashEntry>,
+}
+
+impl HashTable {
+    /// The entry of `key`, keys are compared by the flavor of the table.
+    pub fn entry(&self, key: &ENotation) -> Option<&HashEntry> {
+        self.entries
+            .iter()
+            .find(|entry| self.flavor.same_key(&entry.key, key))
+    }
+
+    /// The value of `key`, keys are compared by the flavor of the table.
+    pub fn get(&self, key: &ENotation) -> Option<&ENotation> {
+        self.entry(key).map(|entry| &entry.value)
+    }
+
+    pub fn contains_key(&self, key: &ENotation) -> bool {
+        self.entry(key).is_some()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &ENotation> {
+        self.entries.iter().map(|entry| &entry.key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &ENotation> {
+        self.entries.iter().map(|entry| &entry.value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Reports every key that is given twice in a hash table of `notation`, with the first one as
+/// related span.
+pub(crate) fn check_keys(notation: &ENotation, errors: &mut Vec<ParseError>) {
+    DuplicateKeys(errors).visit_notation(notation);
+}
+
+struct DuplicateKeys<'e>(&'e mut Vec<ParseError>);
+
+impl Visit for DuplicateKeys<'_> {
+    fn visit_hash_table(&mut self, node: &HashTable) {
+        for (i, entry) in node.entries.iter().enumerate() {
+            let first = node.entries[..i]
+                .iter()
+                .find(|first| node.flavor.same_key(&first.key, &entry.key));
+            if let Some(first) = first {
+                self.0.push(ParseError {
+                    kind: ParseErrorKind::DuplicateHashKey(entry.key.to_string()),
+                    span: entry.key.span.clone(),
+                    expected: vec![],
+                    related: Some(first.key.span.clone()),
+                });
+            }
+        }
+        visit::visit_hash_table(self, node);
+    }
+}
+
+from_pest! {
+    HashTable = Rule::hash => |_, inner| {
+        let opener = inner.next().ok_or(ConversionError::NoMatch)?;
+        let mut entries = vec![];
+        while let Some(elem) = inner.peek() {
+            let entry = HashEntry::of_pair(ENotation::from_pest(inner)?).ok_or_else(|| {
+                let kind = ParseErrorKind::InvalidHashEntry(elem.as_str().to_string());
+                malformed(kind, elem.as_span())
+            })?;
+            entries.push(entry);
+        }
+        Ok(HashTable {
+            flavor: HashFlavor::of_opener(opener.as_str()),
+            entries,
+        })
+    };
+}
+
+impl Display for HashEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({} . {})", self.key, self.value)
+    }
+}
+
+impl Display for HashTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.flavor.opener())?;
+        for (i, entry) in self.entries.iter().enumerate() {
+            if i == 0 {
+                write!(f, "{}", entry)?;
+            } else {
+                write!(f, " {}", entry)?;
+            }
+        }
+        write!(f, ")")
+    }
+}
@@ -1,63 +1,187 @@
 use std::fmt::Display;
 
-use pest_ast::FromPest;
+use from_pest::{ConversionError, FromPest};
+use pest::iterators::Pairs;
+use serde::{Deserialize, Serialize};
 
-use crate::{ENotation, Rule, SetDebugFileName};
+use crate::{
+    convert::{from_pest, malformed, one_of, Conversion},
+    literal::Literal,
+    ENotation, ENotationBody, ParseError, ParseErrorKind, Rule,
+};
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::paren_list))]
+/// `(a b)`, or `(a b . c)` with a dotted tail.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct PList {
     pub elems: Vec<ENotation>,
+    /// The notation after the `.`, the list is improper unless it is a list itself.
+    pub tail: Option<Box<ENotation>>,
 }
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::bracket_list))]
+/// `[a b]`, or `[a b . c]` with a dotted tail.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct BList {
     pub elems: Vec<ENotation>,
+    pub tail: Option<Box<ENotation>>,
 }
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::list))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum List {
     PL(PList),
     BL(BList),
 }
 
-impl SetDebugFileName for PList {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        for elem in self.elems.iter_mut() {
-            elem.set_debug_file_name(file_name);
+/// The arguments of a form, see [`List::arguments`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Arguments<'a> {
+    pub positional: Vec<&'a ENotation>,
+    /// In the order they are given.
+    pub keywords: Vec<KeywordArgument<'a>>,
+}
+
+/// A `#:name value` argument.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeywordArgument<'a> {
+    pub name: &'a str,
+    /// The `#:name` itself, for its span.
+    pub keyword: &'a ENotation,
+    pub value: &'a ENotation,
+}
+
+impl<'a> Arguments<'a> {
+    /// The argument given for `#:name`.
+    pub fn keyword(&self, name: &str) -> Option<&'a ENotation> {
+        self.keywords
+            .iter()
+            .find(|arg| arg.name == name)
+            .map(|arg| arg.value)
+    }
+}
+
+fn keyword_name(notation: &ENotation) -> Option<&str> {
+    match &notation.body {
+        ENotationBody::Literal(Literal::Keyword(k)) => Some(&k.name),
+        _ => None,
+    }
+}
+
+impl List {
+    /// Splits the elements after the head of a form `(head arg ...)` into positional
+    /// arguments and `#:name value` arguments, which can be mixed in any order.
+    ///
+    /// Fails at a keyword that is given twice, with the first one as related span, or that
+    /// has no argument after it.
+    pub fn arguments(&self) -> Result<Arguments<'_>, ParseError> {
+        let mut arguments = Arguments::default();
+        let mut elems = self.elems().iter().skip(1);
+        while let Some(elem) = elems.next() {
+            let Some(name) = keyword_name(elem) else {
+                arguments.positional.push(elem);
+                continue;
+            };
+            let error = |kind| ParseError {
+                kind,
+                span: elem.span.clone(),
+                expected: vec![],
+                related: None,
+            };
+            if let Some(first) = arguments.keywords.iter().find(|arg| arg.name == name) {
+                let mut err = error(ParseErrorKind::DuplicateKeyword(name.to_string()));
+                err.related = Some(first.keyword.span.clone());
+                return Err(err);
+            }
+            match elems.next() {
+                Some(value) if keyword_name(value).is_none() => {
+                    arguments.keywords.push(KeywordArgument {
+                        name,
+                        keyword: elem,
+                        value,
+                    })
+                }
+                _ => {
+                    return Err(error(ParseErrorKind::MissingKeywordArgument(
+                        name.to_string(),
+                    )))
+                }
+            }
         }
+        Ok(arguments)
     }
 }
 
-impl SetDebugFileName for BList {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        for elem in self.elems.iter_mut() {
-            elem.set_debug_file_name(file_name);
+/// The elements of a list, vector or set and the notation after the `.` of a list. A `.` that
+/// is not the single dot of a list between its elements and one last notation is misplaced.
+pub(crate) fn elems_and_tail(
+    inner: &mut Pairs<'_, Rule>,
+    list: bool,
+) -> Conversion<(Vec<ENotation>, Option<Box<ENotation>>)> {
+    let elems: Vec<ENotation> = Vec::from_pest(inner)?;
+    let mut tail = None;
+    // the grammar puts every dotted tail after the elements
+    for dotted in inner.by_ref() {
+        let mut notations = dotted.into_inner();
+        let dot = notations.next().ok_or(ConversionError::NoMatch)?;
+        let reason = if !list {
+            Some("only a list can have a dotted tail")
+        } else if elems.is_empty() {
+            Some("a dotted tail needs an element before it")
+        } else if tail.is_some() {
+            Some("a list has only one dotted tail")
+        } else if notations.clone().count() != 1 {
+            Some("a dotted tail is exactly one notation")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return Err(malformed(
+                ParseErrorKind::MisplacedDot(reason),
+                dot.as_span(),
+            ));
         }
+        tail = Some(Box::from_pest(&mut notations)?);
     }
+    Ok((elems, tail))
 }
 
-impl SetDebugFileName for List {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        match self {
-            List::PL(pl) => pl.set_debug_file_name(file_name),
-            List::BL(bl) => bl.set_debug_file_name(file_name),
+/// The elements of a vector or set, which cannot have a dotted tail.
+pub(crate) fn elems(inner: &mut Pairs<'_, Rule>) -> Conversion<Vec<ENotation>> {
+    elems_and_tail(inner, false).map(|(elems, _)| elems)
+}
+
+from_pest! {
+    PList = Rule::paren_list => |_, inner| {
+        let (elems, tail) = elems_and_tail(inner, true)?;
+        Ok(PList { elems, tail })
+    };
+    BList = Rule::bracket_list => |_, inner| {
+        let (elems, tail) = elems_and_tail(inner, true)?;
+        Ok(BList { elems, tail })
+    };
+    List = Rule::list => |_, inner| one_of!(inner, List::PL, List::BL);
+}
+
+fn write_elems(
+    f: &mut std::fmt::Formatter<'_>,
+    elems: &[ENotation],
+    tail: &Option<Box<ENotation>>,
+) -> std::fmt::Result {
+    for (i, v) in elems.iter().enumerate() {
+        if i == 0 {
+            write!(f, "{}", v)?;
+        } else {
+            write!(f, " {}", v)?;
         }
     }
+    if let Some(tail) = tail {
+        write!(f, " . {}", tail)?;
+    }
+    Ok(())
 }
 
 impl Display for BList {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "[")?;
-        for (i, v) in self.elems.iter().enumerate() {
-            if i == 0 {
-                write!(f, "{}", v)?;
-            } else {
-                write!(f, " {}", v)?;
-            }
-        }
+        write_elems(f, &self.elems, &self.tail)?;
         write!(f, "]")
     }
 }
@@ -65,13 +189,7 @@ impl Display for BList {
 impl Display for PList {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "(")?;
-        for (i, v) in self.elems.iter().enumerate() {
-            if i == 0 {
-                write!(f, "{}", v)?;
-            } else {
-                write!(f, " {}", v)?;
-            }
-        }
+        write_elems(f, &self.elems, &self.tail)?;
         write!(f, ")")
     }
 }
@@ -1,4 +1,4 @@
-use crate::{ENotation, SetDebugFileName};
+use crate::ENotation;
 
 #[cfg(test)]
 mod tests;
@@ -15,30 +15,41 @@ use vector::*;
 pub mod object;
 use object::*;
 
+pub mod bytevector;
+use bytevector::*;
+
+pub mod hash;
+use hash::*;
+
 use std::fmt::Display;
 
-use pest_ast::FromPest;
+use serde::{Deserialize, Serialize};
 
-use crate::Rule;
+use crate::{
+    convert::{from_pest, one_of},
+    Rule,
+};
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::container))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Container {
     List(List),
     Vector(Vector),
     Set(Set),
     Object(Object),
+    Bytevector(Bytevector),
+    Hash(HashTable),
 }
 
-impl SetDebugFileName for Container {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        match self {
-            Container::List(l) => l.set_debug_file_name(file_name),
-            Container::Vector(v) => v.set_debug_file_name(file_name),
-            Container::Set(s) => s.set_debug_file_name(file_name),
-            Container::Object(o) => o.set_debug_file_name(file_name),
-        }
-    }
+from_pest! {
+    Container = Rule::container => |_, inner| one_of!(
+        inner,
+        Container::List,
+        Container::Vector,
+        Container::Set,
+        Container::Object,
+        Container::Bytevector,
+        Container::Hash,
+    );
 }
 
 impl Vector {
@@ -58,6 +69,16 @@ impl List {
     }
 }
 
+impl List {
+    /// The notation after the `.` of a dotted list.
+    pub fn tail(&self) -> Option<&ENotation> {
+        match self {
+            List::PL(plist) => plist.tail.as_deref(),
+            List::BL(blist) => blist.tail.as_deref(),
+        }
+    }
+}
+
 impl Display for Container {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -65,6 +86,8 @@ impl Display for Container {
             Container::Vector(v) => write!(f, "{}", v),
             Container::Set(s) => write!(f, "{}", s),
             Container::Object(o) => write!(f, "{}", o),
+            Container::Bytevector(b) => write!(f, "{}", b),
+            Container::Hash(h) => write!(f, "{}", h),
         }
     }
 }
@@ -1,35 +1,25 @@
 use std::fmt::Display;
 
-use pest_ast::FromPest;
+use serde::{Deserialize, Serialize};
 
-use crate::{literal::Identifier, ENotation, Rule, SetDebugFileName};
+use crate::{convert::from_pest, literal::Identifier, ENotation, Rule};
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::object_pair))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct ObjectPair {
     pub key: Identifier,
     pub value: ENotation,
 }
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::object))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Object {
     pub pairs: Vec<ObjectPair>,
 }
 
-impl SetDebugFileName for ObjectPair {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        // self.key.set_debug_file_name(file_name);
-        self.value.set_debug_file_name(file_name);
-    }
-}
-
-impl SetDebugFileName for Object {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        for pair in self.pairs.iter_mut() {
-            pair.set_debug_file_name(file_name);
-        }
-    }
+from_pest! {
+    ObjectPair = Rule::object_pair => |_, inner| {
+        Ok(ObjectPair { key: Identifier::from_pest(inner)?, value: ENotation::from_pest(inner)? })
+    };
+    Object = Rule::object => |_, inner| Ok(Object { pairs: Vec::from_pest(inner)? });
 }
 
 impl Display for ObjectPair {
@@ -1,20 +1,24 @@
 use std::fmt::Display;
 
-use pest_ast::FromPest;
+use serde::{Deserialize, Serialize};
 
-use crate::{ENotation, Rule, SetDebugFileName};
+use super::list::elems;
+use crate::{convert::from_pest, ENotation, Rule};
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::set))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Set {
     pub elems: Vec<ENotation>,
 }
 
-impl SetDebugFileName for Set {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        for elem in self.elems.iter_mut() {
-            elem.set_debug_file_name(file_name);
-        }
+from_pest! {
+    Set = Rule::set => |_, inner| Ok(Set { elems: elems(inner)? });
+}
+
+impl Set {
+    /// Sorts the elements and drops duplicates, so equal sets have equal elements.
+    pub fn canonicalize(&mut self) {
+        self.elems.sort();
+        self.elems.dedup();
     }
 }
 
@@ -0,0 +1,34 @@
+use insta::assert_snapshot;
+
+use crate::{parse_file, parse_file_recovering, parse_notation, ParseErrorKind};
+
+#[test]
+fn parse_bytevector() {
+    assert_snapshot!(parse_notation("#u8(1 #xff 0 #e2)").unwrap(), @"#u8(1 255 0 2)");
+    assert_snapshot!(parse_notation("#u8()").unwrap(), @"#u8()");
+    // comments between the bytes are fine
+    assert_snapshot!(parse_notation("#u8(1 #;x ; comment\n 2)").unwrap(), @"#u8(1 2)");
+}
+
+#[test]
+fn invalid_byte() {
+    let err = parse_notation("#u8(1 256 a)").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::InvalidByte("256".to_string()));
+    assert_snapshot!(err, @"1:7: `256` is not a byte, expected an integer from 0 to 255");
+    assert_snapshot!(parse_file("(#u8(#i2))").unwrap_err(), @"1:6: `#i2` is not a byte, expected an integer from 0 to 255");
+    assert_snapshot!(parse_file("#u8(1 -3/0)").unwrap_err(), @"1:7: `-3/0` is not a byte, expected an integer from 0 to 255");
+    let (_, errors) = parse_file_recovering("#u8(-1 2)\n#u8(3 (4))");
+    assert_snapshot!(errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("\n"), @r"
+    1:5: `-1` is not a byte, expected an integer from 0 to 255
+    2:7: `(4)` is not a byte, expected an integer from 0 to 255
+    ");
+    assert_snapshot!(parse_file("#u8(1 2").unwrap_err(), @"1:8: unclosed delimiter `#u8(`");
+}
+
+#[test]
+fn lossless_bytevector() {
+    let input = "#u8( 1 #x2 ; three\n 3 ) #\"\\x1;\"\n";
+    let root = crate::cst::parse(input).unwrap();
+    assert_eq!(root.to_string(), input);
+    assert_eq!(crate::cst::lower(&root), parse_file(input).unwrap());
+}
@@ -0,0 +1,97 @@
+use insta::assert_snapshot;
+
+use crate::{
+    container::{hash::HashFlavor, Container},
+    parse_file, parse_file_recovering, parse_notation, ENotation, ENotationBody, ParseErrorKind,
+    Value,
+};
+
+fn table(input: &str) -> crate::container::hash::HashTable {
+    match parse_notation(input).unwrap().body {
+        ENotationBody::Container(Container::Hash(h)) => h,
+        body => panic!("not a hash table: {:?}", body),
+    }
+}
+
+fn key(input: &str) -> ENotation {
+    parse_notation(input).unwrap()
+}
+
+#[test]
+fn parse_hash_table() {
+    assert_snapshot!(parse_notation("#hash((a . 1) [\"b\" . (2 3)])").unwrap(), @r#"#hash((a . 1) ("b" . (2 3)))"#);
+    assert_snapshot!(parse_notation("#hasheqv((1.5 . x))").unwrap(), @"#hasheqv((1.5 . x))");
+    assert_snapshot!(parse_notation("#hasheq()").unwrap(), @"#hasheq()");
+    assert_eq!(table("#hasheq((a . 1))").flavor, HashFlavor::Eq);
+    // a label or any other notation is not an entry
+    assert_snapshot!(parse_notation("#hash((a . 1) (b 2) c)").unwrap_err(), @"1:15: `(b 2)` is not a `(key . value)` pair");
+    let (_, errors) = parse_file_recovering("#hash(#0=(a . 1))\n#hash((b . 2 3))");
+    assert_snapshot!(errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("\n"), @r"
+    1:7: `#0=(a . 1)` is not a `(key . value)` pair
+    2:10: misplaced `.`, a dotted tail is exactly one notation
+    ");
+    assert_snapshot!(parse_file("#hash((a . 1)").unwrap_err(), @"1:14: unclosed delimiter `#hash(`");
+}
+
+#[test]
+fn labeled_entries() {
+    // the table keeps no pair for `#0#` to refer to, only its key and value can be labeled
+    assert_snapshot!(parse_notation("#hash(#0=(a . 1))").unwrap_err(), @"1:7: `#0=(a . 1)` is not a `(key . value)` pair");
+    let labeled = key("#hash((#0=a . #0#))");
+    assert_snapshot!(labeled, @"#hash((#0=a . #0#))");
+    assert_eq!(
+        Value::try_from(&labeled).unwrap(),
+        Value::Hash(
+            HashFlavor::Equal,
+            vec![(Value::symbol("a"), Value::symbol("a"))]
+        )
+    );
+}
+
+#[test]
+fn duplicate_keys() {
+    let err = parse_notation("#hash((1 . a) (#x1 . b))").unwrap_err();
+    assert_eq!(
+        err.kind,
+        ParseErrorKind::DuplicateHashKey("#x1".to_string())
+    );
+    assert_snapshot!(err, @"1:16: duplicate key `#x1` in hash table");
+    assert_snapshot!(err.related.unwrap().span, @"1");
+    assert_snapshot!(parse_notation("#hash(((a) . 1) ([a] . 2))").unwrap_err(), @"1:18: duplicate key `[a]` in hash table");
+    // only atoms are the same key of an eqv table, and only exact integers among the numbers
+    // of an eq table
+    assert!(parse_notation("#hasheqv(((a) . 1) ((a) . 2) (\"s\" . 3) (\"s\" . 4))").is_ok());
+    assert_snapshot!(parse_notation("#hasheqv((1.5 . x) (1.5 . y))").unwrap_err(), @"1:21: duplicate key `1.5` in hash table");
+    assert!(parse_notation("#hasheq((1.5 . x) (1.5 . y))").is_ok());
+    assert_snapshot!(parse_notation("#hasheq((a . 1) (|a| . 2))").unwrap_err(), @"1:18: duplicate key `|a|` in hash table");
+    // nested tables are checked as well, keys and values alike
+    assert_snapshot!(parse_notation("#hash((#hash((k . 1) (k . 2)) . 1))").unwrap_err(), @"1:23: duplicate key `k` in hash table");
+    assert_snapshot!(parse_file("(#hasheq((a . #hash((b . 1) (b . 2)))))").unwrap_err(), @"1:30: duplicate key `b` in hash table");
+    // the recovering parser points the related span at the first key as well
+    let (_, errors) = parse_file_recovering("(a)\n#hash((k . 1) (k . 2))");
+    assert_snapshot!(errors[0], @"2:16: duplicate key `k` in hash table");
+    assert_snapshot!(errors[0].related.as_ref().unwrap().start_line, @"2");
+}
+
+#[test]
+fn lookup() {
+    let h = table("#hash((a . 1) (\"b\" . 2) ((c) . 3))");
+    assert_snapshot!(h.get(&key("|a|")).unwrap(), @"1");
+    assert_snapshot!(h.get(&key("[c]")).unwrap(), @"3");
+    assert!(h.contains_key(&key("\"b\"")));
+    assert!(!h.contains_key(&key("b")));
+    assert_snapshot!(h.keys().map(|k| k.to_string()).collect::<Vec<_>>().join(" "), @r#"a "b" (c)"#);
+    assert_eq!(h.len(), 3);
+
+    let h = table("#hasheqv((\"b\" . 2) (3 . x))");
+    assert!(h.get(&key("\"b\"")).is_none());
+    assert_snapshot!(h.entry(&key("#x3")).unwrap(), @"(3 . x)");
+}
+
+#[test]
+fn lossless_hash_table() {
+    let input = "#hasheqv( (1 . a) ; one\n [2 . b] )\n";
+    let root = crate::cst::parse(input).unwrap();
+    assert_eq!(root.to_string(), input);
+    assert_eq!(crate::cst::lower(&root), parse_file(input).unwrap());
+}
@@ -2,7 +2,7 @@ use from_pest::FromPest;
 use insta::assert_snapshot;
 use pest::Parser;
 
-use crate::ENotationParser;
+use crate::{parse_notation, ENotationParser, ParseErrorKind};
 
 use super::super::*;
 
@@ -40,3 +40,48 @@ fn parse_list() {
     // test nested case
     assert_snapshot!(list("(1 [2 3])"), @"(1 [2 3])");
 }
+
+#[test]
+fn parse_dotted_list() {
+    assert_snapshot!(plist("(a . b)"), @"(a . b)");
+    assert_snapshot!(blist("[a b . (c)]"), @"[a b . (c)]");
+    assert_snapshot!(list("((a . 1)(b . #t))"), @"((a . 1) (b . #t))");
+    assert_snapshot!(list("(a .5 . .5)"), @"(a 0.5 . 0.5)");
+    let l = list("(a b . c)");
+    assert_eq!(l.elems().len(), 2);
+    assert_snapshot!(l.tail().unwrap(), @"c");
+    assert!(list("(a b)").tail().is_none());
+}
+
+#[test]
+fn misplaced_dots() {
+    let err = parse_notation("(. a)").unwrap_err();
+    assert_eq!(
+        err.kind,
+        ParseErrorKind::MisplacedDot("a dotted tail needs an element before it")
+    );
+    assert_snapshot!(err, @"1:2: misplaced `.`, a dotted tail needs an element before it");
+    assert_snapshot!(parse_notation("(a .)").unwrap_err(), @"1:4: misplaced `.`, a dotted tail is exactly one notation");
+    assert_snapshot!(parse_notation("(a . b c)").unwrap_err(), @"1:4: misplaced `.`, a dotted tail is exactly one notation");
+    assert_snapshot!(parse_notation("[a . b . c]").unwrap_err(), @"1:8: misplaced `.`, a list has only one dotted tail");
+    assert_snapshot!(parse_notation("#(a . b)").unwrap_err(), @"1:5: misplaced `.`, only a list can have a dotted tail");
+    assert_snapshot!(parse_notation("#{a . b}").unwrap_err(), @"1:5: misplaced `.`, only a list can have a dotted tail");
+    assert_snapshot!(parse_notation(". a").unwrap_err(), @"1:1: syntax error, expected notation");
+}
+
+#[test]
+fn keyword_arguments() {
+    let form = list("(make-window #:width 80 \"title\" #:height 24 [pane])");
+    let arguments = form.arguments().unwrap();
+    assert_snapshot!(arguments.positional.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(" "), @r#""title" [pane]"#);
+    assert_snapshot!(arguments.keyword("height").unwrap(), @"24");
+    assert!(arguments.keyword("depth").is_none());
+    assert_eq!(arguments.keywords[0].keyword.span.span, "#:width");
+
+    let err = list("(f #:x 1 #:x 2)").arguments().unwrap_err();
+    assert_snapshot!(err, @"1:10: duplicate keyword `#:x`");
+    assert_snapshot!(err.related.unwrap().start_col, @"4");
+    assert_snapshot!(list("(f #:x #:y 1)").arguments().unwrap_err(), @"1:4: missing argument after keyword `#:x`");
+    assert_snapshot!(list("(f 1 #:x)").arguments().unwrap_err(), @"1:6: missing argument after keyword `#:x`");
+    assert!(list("()").arguments().unwrap().positional.is_empty());
+}
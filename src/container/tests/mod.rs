@@ -1,3 +1,5 @@
+mod bytevector;
+mod hash;
 mod list;
 mod object;
 mod set;
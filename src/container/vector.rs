@@ -1,49 +1,33 @@
 use std::fmt::Display;
 
-use pest_ast::FromPest;
+use serde::{Deserialize, Serialize};
 
-use crate::{ENotation, Rule, SetDebugFileName};
+use super::list::elems;
+use crate::{
+    convert::{from_pest, one_of},
+    ENotation, Rule,
+};
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::paren_vector))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct PVector {
     pub elems: Vec<ENotation>,
 }
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::bracket_vector))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct BVector {
     pub elems: Vec<ENotation>,
 }
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::vector))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Vector {
     PV(PVector),
     BV(BVector),
 }
 
-impl SetDebugFileName for PVector {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        for ele in self.elems.iter_mut() {
-            ele.set_debug_file_name(file_name);
-        }
-    }
-}
-impl SetDebugFileName for BVector {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        for ele in self.elems.iter_mut() {
-            ele.set_debug_file_name(file_name);
-        }
-    }
-}
-impl SetDebugFileName for Vector {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        match self {
-            Vector::PV(pvector) => pvector.set_debug_file_name(file_name),
-            Vector::BV(bvector) => bvector.set_debug_file_name(file_name),
-        }
-    }
+from_pest! {
+    PVector = Rule::paren_vector => |_, inner| Ok(PVector { elems: elems(inner)? });
+    BVector = Rule::bracket_vector => |_, inner| Ok(BVector { elems: elems(inner)? });
+    Vector = Rule::vector => |_, inner| one_of!(inner, Vector::PV, Vector::BV);
 }
 
 impl Display for BVector {
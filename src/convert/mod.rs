@@ -0,0 +1,92 @@
+//! The `FromPest` conversions of the AST.
+//!
+//! The grammar accepts more than the AST can hold: a literal out of range, a misplaced `.`, a
+//! bytevector element that is not a byte. Every conversion reports those as
+//! `ConversionError::Malformed` with the [`ParseError`] of the offending pair, so a parse
+//! fails with that error instead of a panic. `NoMatch` and `Extraneous` only mean that a type
+//! was asked to convert a pair of another rule.
+use from_pest::{ConversionError, FromPest};
+use pest::{iterators::Pairs, Span};
+
+use crate::{ParseError, ParseErrorKind, Rule};
+
+pub(crate) type Conversion<T> = Result<T, ConversionError<ParseError>>;
+
+/// Converts the next pair of `pest` if it is a `rule`, `convert` gets its span and the pairs
+/// inside it and must use up all of them.
+pub(crate) fn node<'p, T>(
+    pest: &mut Pairs<'p, Rule>,
+    rule: Rule,
+    convert: impl FnOnce(Span<'p>, &mut Pairs<'p, Rule>) -> Conversion<T>,
+) -> Conversion<T> {
+    let mut rest = pest.clone();
+    let pair = rest.next().ok_or(ConversionError::NoMatch)?;
+    if pair.as_rule() != rule {
+        return Err(ConversionError::NoMatch);
+    }
+    let span = pair.as_span();
+    let mut inner = pair.into_inner();
+    let node = convert(span, &mut inner)?;
+    if inner.next().is_some() {
+        return Err(ConversionError::Extraneous {
+            current_node: std::any::type_name::<T>(),
+        });
+    }
+    *pest = rest;
+    Ok(node)
+}
+
+pub(crate) fn malformed(kind: ParseErrorKind, span: Span<'_>) -> ConversionError<ParseError> {
+    ConversionError::Malformed(ParseError::from_kind(kind, span))
+}
+
+/// The value of the literal at `span`, or the reason it has none.
+pub(crate) fn parsed<T>(
+    span: Span<'_>,
+    parse: fn(Span<'_>) -> Result<T, ParseErrorKind>,
+) -> Conversion<T> {
+    parse(span).map_err(|kind| malformed(kind, span))
+}
+
+/// Converts the pairs of a whole parse, which match the rule of `T`.
+pub(crate) fn convert<'p, T>(pairs: &mut Pairs<'p, Rule>) -> Result<T, ParseError>
+where
+    T: FromPest<'p, Rule = Rule, FatalError = ParseError>,
+{
+    T::from_pest(pairs).map_err(|err| match err {
+        ConversionError::Malformed(err) => err,
+        err => unreachable!("the AST follows the grammar: {}", err),
+    })
+}
+
+/// Implements `FromPest` for each type from the pair of its rule, see [`node`].
+macro_rules! from_pest {
+    ($($ty:ty = $rule:path => |$span:pat_param, $inner:pat_param| $convert:expr;)*) => {$(
+        impl<'pest> from_pest::FromPest<'pest> for $ty {
+            type Rule = $crate::Rule;
+            type FatalError = $crate::ParseError;
+
+            fn from_pest(
+                pest: &mut pest::iterators::Pairs<'pest, $crate::Rule>,
+            ) -> $crate::convert::Conversion<Self> {
+                $crate::convert::node(pest, $rule, |$span, $inner| $convert)
+            }
+        }
+    )*};
+}
+
+/// Converts the next pair of `inner` into the first variant that takes its rule.
+macro_rules! one_of {
+    ($inner:expr, $($variant:path),+ $(,)?) => {{
+        let inner = $inner;
+        Err(from_pest::ConversionError::NoMatch)
+            $(.or_else(|err| match err {
+                from_pest::ConversionError::NoMatch => {
+                    from_pest::FromPest::from_pest(&mut *inner).map($variant)
+                }
+                err => Err(err),
+            }))+
+    }};
+}
+
+pub(crate) use {from_pest, one_of};
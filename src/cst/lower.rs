@@ -0,0 +1,223 @@
+use from_pest::FromPest;
+use pest::Parser;
+
+use super::{SyntaxElement, SyntaxKind, SyntaxNode};
+use crate::{
+    container::{
+        bytevector::Bytevector,
+        hash::{HashEntry, HashFlavor, HashTable},
+        list::{BList, List, PList},
+        object::{Object, ObjectPair},
+        set::Set,
+        vector::{BVector, PVector, Vector},
+        Container,
+    },
+    error::span_of,
+    labeling::{Label, LabelRef, Labeling},
+    literal::Literal,
+    quoting::{QuasiQuote, Quote, Quoting, Unquote, UnquoteSplicing},
+    syntaxing::{QuasiSyntax, Syntax, Syntaxing, Unsyntax, UnsyntaxSplicing},
+    EFile, ENotation, ENotationBody, ENotationParser, Eoi, Rule,
+};
+
+/// Builds the AST of a tree from [`parse`](super::parse), comments and commented notations
+/// are dropped.
+pub fn lower(root: &SyntaxNode) -> EFile {
+    let source = root.text().to_string();
+    EFile {
+        notations: Lowering { source: &source }.elems(root),
+        _eoi: Eoi {},
+    }
+}
+
+/// Builds the AST of a single notation, `None` if `element` is trivia or punctuation.
+///
+/// Spans are relative to the root of the tree `element` belongs to.
+pub fn lower_notation(element: &SyntaxElement) -> Option<ENotation> {
+    let root = match element {
+        SyntaxElement::Node(node) => node.ancestors().last(),
+        SyntaxElement::Token(token) => token.parent_ancestors().last(),
+    }?;
+    Lowering {
+        source: &root.text().to_string(),
+    }
+    .notation(element)
+}
+
+struct Lowering<'s> {
+    source: &'s str,
+}
+
+impl Lowering<'_> {
+    fn notation(&self, element: &SyntaxElement) -> Option<ENotation> {
+        if !element.kind().is_notation() {
+            return None;
+        }
+        let range = element.text_range();
+        let span = span_of(self.source, range.start().into(), range.end().into());
+        let body = match element {
+            SyntaxElement::Token(token) if token.kind() == SyntaxKind::LabelRef => {
+                ENotationBody::Labeling(Labeling::LabelRef(LabelRef {
+                    label: label(token.text()),
+                }))
+            }
+            SyntaxElement::Token(token) => ENotationBody::Literal(literal(token.text())),
+            SyntaxElement::Node(node) => self.node(node),
+        };
+        Some(ENotation { span, body })
+    }
+
+    fn elems(&self, node: &SyntaxNode) -> Vec<ENotation> {
+        node.children_with_tokens()
+            .filter_map(|element| self.notation(&element))
+            .collect()
+    }
+
+    /// The elements of a list and the notation after its `.`, if it has one.
+    fn list(&self, node: &SyntaxNode) -> (Vec<ENotation>, Option<Box<ENotation>>) {
+        let mut elems = self.elems(node);
+        let dotted = node
+            .children_with_tokens()
+            .any(|element| element.kind() == SyntaxKind::Dot);
+        let tail = if dotted {
+            elems.pop().map(Box::new)
+        } else {
+            None
+        };
+        (elems, tail)
+    }
+
+    fn value(&self, node: &SyntaxNode) -> Box<ENotation> {
+        Box::new(
+            self.elems(node)
+                .pop()
+                .expect("quoting, syntaxing and labels always have a notation"),
+        )
+    }
+
+    fn node(&self, node: &SyntaxNode) -> ENotationBody {
+        let opener = node.first_token().map(|token| token.kind());
+        match node.kind() {
+            SyntaxKind::List => {
+                let (elems, tail) = self.list(node);
+                ENotationBody::Container(Container::List(if opener == Some(SyntaxKind::LParen) {
+                    List::PL(PList { elems, tail })
+                } else {
+                    List::BL(BList { elems, tail })
+                }))
+            }
+            SyntaxKind::Vector => ENotationBody::Container(Container::Vector(
+                if opener == Some(SyntaxKind::HashLParen) {
+                    Vector::PV(PVector {
+                        elems: self.elems(node),
+                    })
+                } else {
+                    Vector::BV(BVector {
+                        elems: self.elems(node),
+                    })
+                },
+            )),
+            SyntaxKind::Set => ENotationBody::Container(Container::Set(Set {
+                elems: self.elems(node),
+            })),
+            SyntaxKind::Object => ENotationBody::Container(Container::Object(Object {
+                pairs: node
+                    .children()
+                    .filter(|pair| pair.kind() == SyntaxKind::ObjectPair)
+                    .map(|pair| self.object_pair(&pair))
+                    .collect(),
+            })),
+            SyntaxKind::Bytevector => ENotationBody::Container(Container::Bytevector(Bytevector {
+                bytes: self
+                    .elems(node)
+                    .iter()
+                    .map(|elem| match &elem.body {
+                        ENotationBody::Literal(Literal::Int(i)) => i
+                            .as_i64()
+                            .and_then(|b| u8::try_from(b).ok())
+                            .expect("checked bytevectors only hold bytes"),
+                        _ => unreachable!("checked bytevectors only hold integers"),
+                    })
+                    .collect(),
+            })),
+            SyntaxKind::Hash => ENotationBody::Container(Container::Hash(HashTable {
+                flavor: HashFlavor::of_opener(
+                    node.first_token()
+                        .expect("a hash table starts with its opener")
+                        .text(),
+                ),
+                entries: self
+                    .elems(node)
+                    .into_iter()
+                    .map(|elem| {
+                        HashEntry::of_pair(elem).expect("checked hash tables only hold pairs")
+                    })
+                    .collect(),
+            })),
+            SyntaxKind::Quote => ENotationBody::Quoting(Quoting::Quote(Quote {
+                value: self.value(node),
+            })),
+            SyntaxKind::QuasiQuote => ENotationBody::Quoting(Quoting::QuasiQuote(QuasiQuote {
+                value: self.value(node),
+            })),
+            SyntaxKind::Unquote => ENotationBody::Quoting(Quoting::Unquote(Unquote {
+                value: self.value(node),
+            })),
+            SyntaxKind::UnquoteSplicing => {
+                ENotationBody::Quoting(Quoting::UnquoteSplicing(UnquoteSplicing {
+                    value: self.value(node),
+                }))
+            }
+            SyntaxKind::Syntax => ENotationBody::Syntaxing(Syntaxing::Syntax(Syntax {
+                value: self.value(node),
+            })),
+            SyntaxKind::QuasiSyntax => {
+                ENotationBody::Syntaxing(Syntaxing::QuasiSyntax(QuasiSyntax {
+                    value: self.value(node),
+                }))
+            }
+            SyntaxKind::Unsyntax => ENotationBody::Syntaxing(Syntaxing::Unsyntax(Unsyntax {
+                value: self.value(node),
+            })),
+            SyntaxKind::UnsyntaxSplicing => {
+                ENotationBody::Syntaxing(Syntaxing::UnsyntaxSplicing(UnsyntaxSplicing {
+                    value: self.value(node),
+                }))
+            }
+            SyntaxKind::Label => ENotationBody::Labeling(Labeling::Label(Label {
+                label: label(
+                    node.first_token()
+                        .expect("a label starts with its `#n=`")
+                        .text(),
+                ),
+                value: self.value(node),
+            })),
+            kind => unreachable!("{:?} is not a notation", kind),
+        }
+    }
+
+    fn object_pair(&self, pair: &SyntaxNode) -> ObjectPair {
+        let mut elems = self.elems(pair).into_iter();
+        let key = match elems.next().map(|key| key.body) {
+            Some(ENotationBody::Literal(Literal::Identifier(key))) => key,
+            _ => unreachable!("an object key is always an identifier"),
+        };
+        ObjectPair {
+            key,
+            value: elems.next().expect("an object pair always has a value"),
+        }
+    }
+}
+
+/// The number of a `#n=` or `#n#`, checked when the tree was built.
+fn label(text: &str) -> u64 {
+    text[1..text.len() - 1]
+        .parse()
+        .expect("checked labels always convert")
+}
+
+/// Atoms were checked when the tree was built, so converting them again cannot fail.
+fn literal(text: &str) -> Literal {
+    let mut pairs = ENotationParser::parse(Rule::literal, text).expect("atoms are literals");
+    Literal::from_pest(&mut pairs).expect("checked atoms always convert")
+}
@@ -0,0 +1,400 @@
+//! A lossless syntax tree, for tools that rewrite files and must keep comments and formatting.
+//!
+//! The tree is a [rowan] green/red tree: every byte of the input, including whitespace,
+//! comments and `#;` commented notations, is a token of the tree, so printing the root
+//! reproduces the input. [`lower`] turns it into the usual [`EFile`](crate::EFile).
+use pest::iterators::Pair;
+use pest::Parser;
+use rowan::{GreenNodeBuilder, Language};
+
+use crate::{
+    convert::convert, error::delimiter::block_comment_len, parse_pairs, EFile, ENotationParser,
+    ParseError, Rule,
+};
+
+mod lower;
+pub use lower::{lower, lower_notation};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u16)]
+pub enum SyntaxKind {
+    // trivia
+    Whitespace,
+    LineComment,
+    BlockComment,
+    /// The `#;` of a commented notation.
+    DatumCommentMark,
+
+    // punctuation
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+    HashLParen,
+    HashLBracket,
+    HashLBrace,
+    HashU8LParen,
+    /// The `#hash(`, `#hasheqv(` or `#hasheq(` of a hash table.
+    HashOpen,
+    Colon,
+    Comma,
+    /// The `.` before the tail of a list.
+    Dot,
+    QuoteMark,
+    QuasiQuoteMark,
+    UnquoteMark,
+    UnquoteSplicingMark,
+    SyntaxMark,
+    QuasiSyntaxMark,
+    UnsyntaxMark,
+    UnsyntaxSplicingMark,
+    /// The `#n=` of a label.
+    LabelMark,
+
+    // atoms
+    Boolean,
+    Char,
+    Int,
+    Rational,
+    Float,
+    Complex,
+    String,
+    ByteString,
+    Identifier,
+    Keyword,
+    LabelRef,
+
+    // nodes
+    List,
+    Vector,
+    Set,
+    Object,
+    ObjectPair,
+    Bytevector,
+    Hash,
+    Quote,
+    QuasiQuote,
+    Unquote,
+    UnquoteSplicing,
+    Syntax,
+    QuasiSyntax,
+    Unsyntax,
+    UnsyntaxSplicing,
+    Label,
+    /// `#;` and the notation it comments out.
+    DatumComment,
+    Root,
+}
+
+use SyntaxKind::*;
+
+/// Every kind, indexed by its raw value.
+const KINDS: [SyntaxKind; 56] = [
+    Whitespace,
+    LineComment,
+    BlockComment,
+    DatumCommentMark,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+    HashLParen,
+    HashLBracket,
+    HashLBrace,
+    HashU8LParen,
+    HashOpen,
+    Colon,
+    Comma,
+    Dot,
+    QuoteMark,
+    QuasiQuoteMark,
+    UnquoteMark,
+    UnquoteSplicingMark,
+    SyntaxMark,
+    QuasiSyntaxMark,
+    UnsyntaxMark,
+    UnsyntaxSplicingMark,
+    LabelMark,
+    Boolean,
+    Char,
+    Int,
+    Rational,
+    Float,
+    Complex,
+    String,
+    ByteString,
+    Identifier,
+    Keyword,
+    LabelRef,
+    List,
+    Vector,
+    Set,
+    Object,
+    ObjectPair,
+    Bytevector,
+    Hash,
+    Quote,
+    QuasiQuote,
+    Unquote,
+    UnquoteSplicing,
+    Syntax,
+    QuasiSyntax,
+    Unsyntax,
+    UnsyntaxSplicing,
+    Label,
+    DatumComment,
+    Root,
+];
+
+impl SyntaxKind {
+    /// Whitespace and comments, everything a notation ignores.
+    pub fn is_trivia(self) -> bool {
+        matches!(
+            self,
+            Whitespace | LineComment | BlockComment | DatumCommentMark | DatumComment
+        )
+    }
+
+    /// Tokens and nodes that lower to an `ENotation`.
+    pub fn is_notation(self) -> bool {
+        matches!(
+            self,
+            Boolean
+                | Char
+                | Int
+                | Rational
+                | Float
+                | Complex
+                | String
+                | ByteString
+                | Identifier
+                | Keyword
+                | LabelRef
+                | List
+                | Vector
+                | Set
+                | Object
+                | Bytevector
+                | Hash
+                | Quote
+                | QuasiQuote
+                | Unquote
+                | UnquoteSplicing
+                | Syntax
+                | QuasiSyntax
+                | Unsyntax
+                | UnsyntaxSplicing
+                | Label
+        )
+    }
+}
+
+impl From<SyntaxKind> for rowan::SyntaxKind {
+    fn from(kind: SyntaxKind) -> Self {
+        Self(kind as u16)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ENotationLanguage {}
+
+impl Language for ENotationLanguage {
+    type Kind = SyntaxKind;
+
+    fn kind_from_raw(raw: rowan::SyntaxKind) -> SyntaxKind {
+        KINDS[raw.0 as usize]
+    }
+
+    fn kind_to_raw(kind: SyntaxKind) -> rowan::SyntaxKind {
+        kind.into()
+    }
+}
+
+pub type SyntaxNode = rowan::SyntaxNode<ENotationLanguage>;
+pub type SyntaxToken = rowan::SyntaxToken<ENotationLanguage>;
+pub type SyntaxElement = rowan::SyntaxElement<ENotationLanguage>;
+
+/// Parses a whole file into a lossless tree, `root.to_string()` is always `input`.
+pub fn parse(input: &str) -> Result<SyntaxNode, ParseError> {
+    let mut pairs = parse_pairs(Rule::file, input)?;
+    convert::<EFile>(&mut pairs.clone())?;
+    let file = pairs.next().expect("a file always matches");
+    let mut builder = Builder {
+        input,
+        pos: 0,
+        base: 0,
+        parents: vec![],
+        green: GreenNodeBuilder::new(),
+    };
+    builder.start_node(Root);
+    for pair in file.into_inner() {
+        builder.pair(pair);
+    }
+    builder.gap(input.len());
+    builder.finish_node();
+    Ok(SyntaxNode::new_root(builder.green.finish()))
+}
+
+/// Punctuation, longest first so that `#,@` is not read as `#,` and `@`.
+const PUNCTUATION: [(&str, SyntaxKind); 20] = [
+    ("#u8(", HashU8LParen),
+    ("#,@", UnsyntaxSplicingMark),
+    (",@", UnquoteSplicingMark),
+    ("#(", HashLParen),
+    ("#[", HashLBracket),
+    ("#{", HashLBrace),
+    ("#'", SyntaxMark),
+    ("#`", QuasiSyntaxMark),
+    ("#,", UnsyntaxMark),
+    ("(", LParen),
+    (")", RParen),
+    ("[", LBracket),
+    ("]", RBracket),
+    ("{", LBrace),
+    ("}", RBrace),
+    ("'", QuoteMark),
+    ("`", QuasiQuoteMark),
+    (",", UnquoteMark),
+    (":", Colon),
+    (".", Dot),
+];
+
+/// Walks the pest pairs and lexes the text between them, which the grammar does not keep.
+struct Builder<'i> {
+    input: &'i str,
+    /// Everything before `pos` is already in the tree.
+    pos: usize,
+    /// Where the input of the pairs being walked starts, commented notations are parsed on
+    /// their own.
+    base: usize,
+    parents: Vec<SyntaxKind>,
+    green: GreenNodeBuilder<'static>,
+}
+
+impl Builder<'_> {
+    fn start_node(&mut self, kind: SyntaxKind) {
+        self.parents.push(kind);
+        self.green.start_node(kind.into());
+    }
+
+    fn finish_node(&mut self) {
+        self.parents.pop();
+        self.green.finish_node();
+    }
+
+    fn token(&mut self, kind: SyntaxKind, end: usize) {
+        self.green.token(kind.into(), &self.input[self.pos..end]);
+        self.pos = end;
+    }
+
+    fn pair(&mut self, pair: Pair<'_, Rule>) {
+        let (start, end) = (
+            self.base + pair.as_span().start(),
+            self.base + pair.as_span().end(),
+        );
+        let atom = match pair.as_rule() {
+            Rule::boolean => Boolean,
+            Rule::char => Char,
+            Rule::int => Int,
+            Rule::rational => Rational,
+            Rule::float => Float,
+            Rule::complex => Complex,
+            Rule::string => String,
+            Rule::byte_string => ByteString,
+            Rule::identifier => Identifier,
+            Rule::keyword => Keyword,
+            Rule::label_def => LabelMark,
+            Rule::hash_open => HashOpen,
+            Rule::label_ref => LabelRef,
+            rule => {
+                let kind = match rule {
+                    Rule::paren_list | Rule::bracket_list => List,
+                    Rule::paren_vector | Rule::bracket_vector => Vector,
+                    Rule::set => Set,
+                    Rule::object => Object,
+                    Rule::object_pair => ObjectPair,
+                    Rule::bytevector => Bytevector,
+                    Rule::hash => Hash,
+                    Rule::quote => Quote,
+                    Rule::quasiquote => QuasiQuote,
+                    Rule::unquote => Unquote,
+                    Rule::unquote_splicing => UnquoteSplicing,
+                    Rule::syntax => Syntax,
+                    Rule::quasisyntax => QuasiSyntax,
+                    Rule::unsyntax => Unsyntax,
+                    Rule::unsyntax_splicing => UnsyntaxSplicing,
+                    Rule::label => Label,
+                    // wrappers like `notation` and `container` have no syntax of their own
+                    _ => {
+                        for inner in pair.into_inner() {
+                            self.pair(inner);
+                        }
+                        return;
+                    }
+                };
+                self.gap(start);
+                self.start_node(kind);
+                for inner in pair.into_inner() {
+                    self.pair(inner);
+                }
+                self.gap(end);
+                self.finish_node();
+                return;
+            }
+        };
+        self.gap(start);
+        self.token(atom, end);
+    }
+
+    /// Turns the text up to `end` into trivia and punctuation tokens.
+    fn gap(&mut self, end: usize) {
+        while self.pos < end {
+            let rest = &self.input[self.pos..end];
+            let whitespace = rest
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or(rest.len());
+            if whitespace > 0 {
+                self.token(Whitespace, self.pos + whitespace);
+            } else if rest.starts_with(';') {
+                let len = rest.find(['\r', '\n']).unwrap_or(rest.len());
+                self.token(LineComment, self.pos + len);
+            } else if rest.starts_with("#|") {
+                let len =
+                    block_comment_len(rest).expect("the grammar only accepts closed comments");
+                self.token(BlockComment, self.pos + len);
+            } else if rest.starts_with("#;") {
+                self.datum_comment();
+            } else if let Some((text, kind)) = PUNCTUATION.iter().find(|(p, _)| rest.starts_with(p))
+            {
+                let kind = match kind {
+                    UnquoteMark if self.parents.last() == Some(&Object) => Comma,
+                    kind => *kind,
+                };
+                self.token(kind, self.pos + text.len());
+            } else {
+                unreachable!("the grammar only leaves trivia and punctuation between notations")
+            }
+        }
+    }
+
+    fn datum_comment(&mut self) {
+        self.start_node(DatumComment);
+        self.token(DatumCommentMark, self.pos + 2);
+        let start = self.pos;
+        let notation = ENotationParser::parse(Rule::notation, &self.input[start..])
+            .expect("the grammar already accepted the commented notation")
+            .next()
+            .expect("a notation always matches");
+        let base = std::mem::replace(&mut self.base, start);
+        self.pair(notation);
+        self.base = base;
+        self.finish_node();
+    }
+}
@@ -0,0 +1,92 @@
+use insta::{assert_debug_snapshot, assert_snapshot};
+
+use super::{lower, parse, SyntaxKind};
+
+const SOURCE: &str = "; a configuration
+(define x   1) ; trailing
+#| block #| nested |# |#
+#;(ignored) [let ([y #e1.5])
+  '(a ,@b #'c)]
+{key : #(1 2), other :#{\"s\"},}
+";
+
+#[test]
+fn print_is_lossless() {
+    let root = parse(SOURCE).unwrap();
+    assert_eq!(root.to_string(), SOURCE);
+    let tree = parse("(a #;b ; c\n 'd)").unwrap();
+    assert_debug_snapshot!(tree, @r##"
+    Root@0..15
+      List@0..15
+        LParen@0..1 "("
+        Identifier@1..2 "a"
+        Whitespace@2..3 " "
+        DatumComment@3..6
+          DatumCommentMark@3..5 "#;"
+          Identifier@5..6 "b"
+        Whitespace@6..7 " "
+        LineComment@7..10 "; c"
+        Whitespace@10..12 "\n "
+        Quote@12..14
+          QuoteMark@12..13 "'"
+          Identifier@13..14 "d"
+        RParen@14..15 ")"
+    "##);
+}
+
+#[test]
+fn lossless_dotted_list() {
+    let input = "(a . ; tail\n b)";
+    let root = parse(input).unwrap();
+    assert_eq!(root.to_string(), input);
+    assert_debug_snapshot!(root, @r#"
+    Root@0..15
+      List@0..15
+        LParen@0..1 "("
+        Identifier@1..2 "a"
+        Whitespace@2..3 " "
+        Dot@3..4 "."
+        Whitespace@4..5 " "
+        LineComment@5..11 "; tail"
+        Whitespace@11..13 "\n "
+        Identifier@13..14 "b"
+        RParen@14..15 ")"
+    "#);
+    assert_eq!(lower(&root), crate::parse_file(input).unwrap());
+}
+
+#[test]
+fn lower_to_ast() {
+    let root = parse(SOURCE).unwrap();
+    let file = lower(&root);
+    assert_snapshot!(file, @r#"
+    (define x 1)
+    [let ([y #e1.5]) '(a ,@b #'c)]
+    {key : #(1 2), other : #{"s"}}
+    "#);
+    let expected = crate::parse_file(SOURCE).unwrap();
+    assert_eq!(format!("{:?}", file), format!("{:?}", expected));
+}
+
+#[test]
+fn comments_are_tokens() {
+    let root = parse(SOURCE).unwrap();
+    let comments = root
+        .descendants_with_tokens()
+        .filter(|element| {
+            matches!(
+                element.kind(),
+                SyntaxKind::LineComment | SyntaxKind::BlockComment | SyntaxKind::DatumComment
+            )
+        })
+        .map(|element| element.to_string())
+        .collect::<Vec<_>>();
+    assert_debug_snapshot!(comments, @r##"
+    [
+        "; a configuration",
+        "; trailing",
+        "#| block #| nested |# |#",
+        "#;(ignored)",
+    ]
+    "##);
+}
@@ -0,0 +1,517 @@
+//! Deserializes Rust values from e-notation with serde.
+//!
+//! Structs and maps are read from objects (`{name : "x", port : 80}`), maps also from hash
+//! tables with keys of any kind (`#hash((1 . "one"))`), sequences and tuples
+//! from lists, vectors and sets, bytes from byte strings and bytevectors, `None` and `()`
+//! from the nil form `()`, and enum variants from an identifier (`Red`) or a tagged list
+//! (`(Rgb 1 2 3)`, `(Point {x : 1, y : 2})`). The tail of a dotted list is its last element,
+//! so the pairs of `((a . 1) (b . 2))` read as tuples.
+use std::fmt::Display;
+
+use num::ToPrimitive;
+use serde::de::{
+    self,
+    value::{SeqDeserializer, StrDeserializer},
+    DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor,
+};
+
+use crate::{
+    container::{hash::HashEntry, list::List, object::ObjectPair, Container},
+    literal::Literal,
+    parse_notation, DiagnosticSpan, ENotation, ENotationBody, ParseError,
+};
+
+mod spanned;
+#[cfg(test)]
+mod tests;
+
+pub use spanned::Spanned;
+
+/// Reads a `T` from a source holding exactly one notation.
+pub fn from_str<T: DeserializeOwned>(input: &str) -> Result<T, Error> {
+    from_notation(&parse_notation(input)?)
+}
+
+/// Reads a `T` from an already parsed notation.
+pub fn from_notation<T: DeserializeOwned>(notation: &ENotation) -> Result<T, Error> {
+    T::deserialize(Deserializer { notation })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub message: String,
+    /// The notation that could not be deserialized.
+    pub span: Option<DiagnosticSpan>,
+}
+
+impl Error {
+    /// Blames `notation` unless a notation inside it is already blamed.
+    fn at(mut self, notation: &ENotation) -> Self {
+        if self.span.is_none() {
+            self.span = Some(notation.span.clone());
+        }
+        self
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error {
+            message: msg.to_string(),
+            span: None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error {
+            message: err.kind.to_string(),
+            span: Some(err.span),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(span) = &self.span {
+            write!(f, "{}:{}: ", span.start_line, span.start_col)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A serde deserializer over one notation.
+pub struct Deserializer<'a> {
+    notation: &'a ENotation,
+}
+
+impl<'a> Deserializer<'a> {
+    pub fn new(notation: &'a ENotation) -> Self {
+        Self { notation }
+    }
+
+    fn error(&self, expected: &str) -> Error {
+        Error {
+            message: format!("expected {}, found `{}`", expected, self.notation),
+            span: Some(self.notation.span.clone()),
+        }
+    }
+
+    fn is_nil(&self) -> bool {
+        elems(self.notation).is_some_and(|elems| elems.is_empty())
+            && matches!(
+                self.notation.body,
+                ENotationBody::Container(Container::List(List::PL(_)))
+            )
+    }
+}
+
+/// The elements of a list, vector or set.
+fn elems(notation: &ENotation) -> Option<&[ENotation]> {
+    match &notation.body {
+        ENotationBody::Container(Container::List(l)) => Some(l.elems()),
+        ENotationBody::Container(Container::Vector(v)) => Some(v.elems()),
+        ENotationBody::Container(Container::Set(s)) => Some(&s.elems),
+        _ => None,
+    }
+}
+
+/// The tail of a dotted list, which is read as its last element.
+fn tail(notation: &ENotation) -> Option<&ENotation> {
+    match &notation.body {
+        ENotationBody::Container(Container::List(l)) => l.tail(),
+        _ => None,
+    }
+}
+
+/// The bytes of a byte string or bytevector, which also read as a sequence of integers.
+fn bytes(notation: &ENotation) -> Option<&[u8]> {
+    match &notation.body {
+        ENotationBody::Literal(Literal::ByteString(b)) => Some(&b.value),
+        ENotationBody::Container(Container::Bytevector(b)) => Some(&b.bytes),
+        _ => None,
+    }
+}
+
+fn identifier(notation: &ENotation) -> Option<&str> {
+    match &notation.body {
+        ENotationBody::Literal(Literal::Identifier(i)) => Some(i.symbol()),
+        _ => None,
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let notation = self.notation;
+        let result = match &notation.body {
+            ENotationBody::Literal(literal) => match literal {
+                Literal::Boolean(b) => visitor.visit_bool(b.value),
+                Literal::Int(i) => {
+                    let value = i.as_bigint();
+                    if let Some(value) = value.to_i64() {
+                        visitor.visit_i64(value)
+                    } else if let Some(value) = value.to_u64() {
+                        visitor.visit_u64(value)
+                    } else if let Some(value) = value.to_i128() {
+                        visitor.visit_i128(value)
+                    } else if let Some(value) = value.to_u128() {
+                        visitor.visit_u128(value)
+                    } else {
+                        Err(self.error("an integer that fits into 128 bits"))
+                    }
+                }
+                Literal::Rational(r) => visitor.visit_f64(r.to_f64()),
+                Literal::Float(f) => visitor.visit_f64(f.value),
+                Literal::Char(c) => visitor.visit_char(c.value),
+                Literal::String_(s) => visitor.visit_str(&s.value),
+                Literal::Identifier(i) => visitor.visit_str(i.symbol()),
+                Literal::Keyword(k) => visitor.visit_str(&k.name),
+                Literal::ByteString(b) => visitor.visit_bytes(&b.value),
+                Literal::Complex(_) => Err(self.error("a real number")),
+            },
+            ENotationBody::Container(Container::Object(o)) => {
+                visitor.visit_map(MapAccess::new(&o.pairs))
+            }
+            ENotationBody::Container(Container::Bytevector(b)) => visitor.visit_bytes(&b.bytes),
+            ENotationBody::Container(Container::Hash(h)) => {
+                visitor.visit_map(EntryAccess::new(&h.entries))
+            }
+            _ if self.is_nil() => visitor.visit_unit(),
+            ENotationBody::Container(_) => visitor.visit_seq(
+                SeqAccess::new(
+                    elems(notation).expect("containers other than objects have elements"),
+                )
+                .with_tail(tail(notation)),
+            ),
+            _ => Err(self.error("a literal or a container")),
+        };
+        result.map_err(|err| err.at(notation))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.is_nil() {
+            visitor.visit_none()
+        } else {
+            let notation = self.notation;
+            visitor.visit_some(self).map_err(|err| err.at(notation))
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.is_nil() {
+            visitor.visit_unit()
+        } else {
+            Err(self.error("`()`"))
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if let Some(bytes) = bytes(self.notation) {
+            return SeqDeserializer::<_, Error>::new(bytes.iter().copied())
+                .deserialize_any(visitor)
+                .map_err(|err| err.at(self.notation));
+        }
+        match elems(self.notation) {
+            Some(elems) => visitor
+                .visit_seq(SeqAccess::new(elems).with_tail(tail(self.notation)))
+                .map_err(|err| err.at(self.notation)),
+            None => Err(self.error("a list, vector or set")),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match &self.notation.body {
+            ENotationBody::Container(Container::Object(o)) => visitor
+                .visit_map(MapAccess::new(&o.pairs))
+                .map_err(|err| err.at(self.notation)),
+            ENotationBody::Container(Container::Hash(h)) => visitor
+                .visit_map(EntryAccess::new(&h.entries))
+                .map_err(|err| err.at(self.notation)),
+            _ => Err(self.error("an object or a hash table")),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if name == spanned::NAME {
+            return visitor.visit_map(spanned::SpannedAccess {
+                notation: self.notation,
+                next: Some(spanned::SPAN),
+            });
+        }
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let notation = self.notation;
+        let access = if let Some(name) = identifier(notation) {
+            EnumAccess {
+                notation,
+                name,
+                args: &[],
+            }
+        } else {
+            match elems(notation) {
+                Some([head, args @ ..])
+                    if identifier(head).is_some() && tail(notation).is_none() =>
+                {
+                    EnumAccess {
+                        notation,
+                        name: identifier(head).expect("checked above"),
+                        args,
+                    }
+                }
+                _ => return Err(self.error("a variant name or a list tagged with one")),
+            }
+        };
+        visitor.visit_enum(access).map_err(|err| err.at(notation))
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match &self.notation.body {
+            ENotationBody::Literal(Literal::Identifier(i)) => visitor.visit_str(i.symbol()),
+            ENotationBody::Literal(Literal::String_(s)) => visitor.visit_str(&s.value),
+            _ => Err(self.error("an identifier")),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf
+    }
+}
+
+struct SeqAccess<'a> {
+    elems: std::slice::Iter<'a, ENotation>,
+    tail: Option<&'a ENotation>,
+}
+
+impl<'a> SeqAccess<'a> {
+    fn new(elems: &'a [ENotation]) -> Self {
+        Self {
+            elems: elems.iter(),
+            tail: None,
+        }
+    }
+
+    fn with_tail(self, tail: Option<&'a ENotation>) -> Self {
+        Self { tail, ..self }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess<'_> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        self.elems
+            .next()
+            .or_else(|| self.tail.take())
+            .map(|notation| seed.deserialize(Deserializer { notation }))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.elems.len() + usize::from(self.tail.is_some()))
+    }
+}
+
+struct MapAccess<'a> {
+    pairs: std::slice::Iter<'a, ObjectPair>,
+    value: Option<&'a ENotation>,
+}
+
+impl<'a> MapAccess<'a> {
+    fn new(pairs: &'a [ObjectPair]) -> Self {
+        Self {
+            pairs: pairs.iter(),
+            value: None,
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.pairs.next() {
+            Some(pair) => {
+                self.value = Some(&pair.value);
+                let key: StrDeserializer<'_, Error> = pair.key.symbol().into_deserializer();
+                seed.deserialize(key)
+                    .map(Some)
+                    .map_err(|err| err.at(&pair.value))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let notation = self.value.take().expect("serde asks for a key first");
+        seed.deserialize(Deserializer { notation })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.pairs.len())
+    }
+}
+
+/// The entries of a hash table, unlike the keys of an object their keys are notations.
+struct EntryAccess<'a> {
+    entries: std::slice::Iter<'a, HashEntry>,
+    value: Option<&'a ENotation>,
+}
+
+impl<'a> EntryAccess<'a> {
+    fn new(entries: &'a [HashEntry]) -> Self {
+        Self {
+            entries: entries.iter(),
+            value: None,
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for EntryAccess<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.entries.next() {
+            Some(entry) => {
+                self.value = Some(&entry.value);
+                seed.deserialize(Deserializer {
+                    notation: &entry.key,
+                })
+                .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let notation = self.value.take().expect("serde asks for a key first");
+        seed.deserialize(Deserializer { notation })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// A variant, written as its name or as a list tagged with its name.
+struct EnumAccess<'a> {
+    notation: &'a ENotation,
+    name: &'a str,
+    args: &'a [ENotation],
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for EnumAccess<'a> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+        let name: StrDeserializer<'_, Error> = self.name.into_deserializer();
+        Ok((seed.deserialize(name)?, self))
+    }
+}
+
+impl<'a> EnumAccess<'a> {
+    fn error(&self, expected: &str) -> Error {
+        Error {
+            message: format!("variant `{}` expects {}", self.name, expected),
+            span: Some(self.notation.span.clone()),
+        }
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for EnumAccess<'_> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        if self.args.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("no arguments"))
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        match self.args {
+            [notation] => seed.deserialize(Deserializer { notation }),
+            _ => Err(self.error("one argument")),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(SeqAccess::new(self.args))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.args {
+            [notation] => de::Deserializer::deserialize_map(Deserializer { notation }, visitor),
+            _ => Err(self.error("an object")),
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::fmt::Display;
+
+use serde::{
+    de::{self, value::MapDeserializer, DeserializeSeed, IntoDeserializer, Visitor},
+    forward_to_deserialize_any, Deserialize, Serialize,
+};
+
+use super::{Deserializer, Error};
+use crate::{DiagnosticSpan, ENotation};
+
+/// The struct name [`Deserializer`] recognizes to hand out the span of a notation.
+pub(super) const NAME: &str = "$__enotation_private_Spanned";
+pub(super) const SPAN: &str = "$__enotation_private_span";
+const VALUE: &str = "$__enotation_private_value";
+
+/// A deserialized value with the span of the notation it was read from, so a value that is
+/// well-formed but wrong can still be reported at its place in the file.
+///
+/// Only [`from_str`](super::from_str) and [`from_notation`](super::from_notation) know the
+/// spans, other deserializers fail on it. Comparisons ignore the span, serializing writes the
+/// value alone.
+#[derive(Debug, Clone)]
+pub struct Spanned<T> {
+    span: DiagnosticSpan,
+    value: T,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(span: DiagnosticSpan, value: T) -> Self {
+        Self { span, value }
+    }
+
+    pub fn span(&self) -> &DiagnosticSpan {
+        &self.span
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.value
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: PartialEq> PartialEq for Spanned<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for Spanned<T> {}
+
+impl<T: Serialize> Serialize for Spanned<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Spanned<T> {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SpannedVisitor<T>(std::marker::PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for SpannedVisitor<T> {
+            type Value = Spanned<T>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a notation with its span")
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Spanned<T>, A::Error> {
+                if map.next_key::<String>()?.as_deref() != Some(SPAN) {
+                    return Err(de::Error::custom("spans are only known to enotation"));
+                }
+                let span = map.next_value()?;
+                if map.next_key::<String>()?.as_deref() != Some(VALUE) {
+                    return Err(de::Error::custom("spans are only known to enotation"));
+                }
+                let value = map.next_value()?;
+                Ok(Spanned { span, value })
+            }
+        }
+
+        deserializer.deserialize_struct(
+            NAME,
+            &[SPAN, VALUE],
+            SpannedVisitor(std::marker::PhantomData),
+        )
+    }
+}
+
+impl<T: Display> Display for Spanned<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+/// Hands out the span, then the notation itself.
+pub(super) struct SpannedAccess<'a> {
+    pub(super) notation: &'a ENotation,
+    pub(super) next: Option<&'static str>,
+}
+
+impl<'de> de::MapAccess<'de> for SpannedAccess<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        self.next
+            .map(|key| seed.deserialize(key.into_deserializer()))
+            .transpose()
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        match self.next {
+            Some(SPAN) => {
+                self.next = Some(VALUE);
+                seed.deserialize(SpanDeserializer(&self.notation.span))
+            }
+            _ => {
+                self.next = None;
+                seed.deserialize(Deserializer::new(self.notation))
+            }
+        }
+    }
+}
+
+/// Reads a [`DiagnosticSpan`] field by field.
+struct SpanDeserializer<'a>(&'a DiagnosticSpan);
+
+impl<'de> de::Deserializer<'de> for SpanDeserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let span = self.0;
+        let fields = [
+            ("start_line", Field::Usize(span.start_line)),
+            ("start_col", Field::Usize(span.start_col)),
+            ("start_offset", Field::Usize(span.start_offset)),
+            ("end_line", Field::Usize(span.end_line)),
+            ("end_col", Field::Usize(span.end_col)),
+            ("end_offset", Field::Usize(span.end_offset)),
+            ("span", Field::Str(&span.span)),
+            ("file", Field::File(span.file.as_deref())),
+        ];
+        visitor.visit_map(MapDeserializer::new(fields.into_iter()))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// A field of a [`DiagnosticSpan`].
+enum Field<'a> {
+    Usize(usize),
+    Str(&'a str),
+    File(Option<&'a str>),
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Field<'_> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Field<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Field::Usize(n) => visitor.visit_u64(n as u64),
+            Field::Str(s) => visitor.visit_str(s),
+            Field::File(None) => visitor.visit_none(),
+            Field::File(Some(file)) => visitor.visit_some(Field::Str(file)),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use insta::{assert_debug_snapshot, assert_snapshot};
+use serde::Deserialize;
+
+use crate::{from_str, Spanned};
+
+// The fields are only read through `Debug`.
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+struct Server {
+    name: String,
+    port: u16,
+    tags: Vec<String>,
+    backup: Option<Box<Server>>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Rect(u32, u32),
+    Labeled { label: char, shape: Box<Shape> },
+}
+
+#[test]
+fn deserialize_struct() {
+    let server: Server = from_str(
+        r#"{name : "main", port : 80, tags : ["a" "b"],
+            backup : {name : "spare", port : 8080, tags : #(), backup : ()}}"#,
+    )
+    .unwrap();
+    assert_debug_snapshot!(server, @r#"
+    Server {
+        name: "main",
+        port: 80,
+        tags: [
+            "a",
+            "b",
+        ],
+        backup: Some(
+            Server {
+                name: "spare",
+                port: 8080,
+                tags: [],
+                backup: None,
+            },
+        ),
+    }
+    "#);
+}
+
+#[test]
+fn deserialize_collections() {
+    let list: Vec<i64> = from_str("(1 2 -3)").unwrap();
+    let set: BTreeSet<u8> = from_str("#{3 1 2}").unwrap();
+    let tuple: (bool, char, f64, String) = from_str(r#"#[#t #\a 1/2 "s"]"#).unwrap();
+    let map: BTreeMap<String, Option<i32>> = from_str("{a : 1, b : ()}").unwrap();
+    assert_debug_snapshot!((list, set, tuple, map), @r#"
+    (
+        [
+            1,
+            2,
+            -3,
+        ],
+        {
+            1,
+            2,
+            3,
+        },
+        (
+            true,
+            'a',
+            0.5,
+            "s",
+        ),
+        {
+            "a": Some(
+                1,
+            ),
+            "b": None,
+        },
+    )
+    "#);
+}
+
+#[test]
+fn deserialize_dotted_pairs() {
+    let alist: Vec<(String, i32)> = from_str("((a . 1) (b . 2))").unwrap();
+    assert_debug_snapshot!(alist, @r#"
+    [
+        (
+            "a",
+            1,
+        ),
+        (
+            "b",
+            2,
+        ),
+    ]
+    "#);
+    assert_snapshot!(from_str::<Shape>("(Circle . 1.0)").unwrap_err(), @"1:1: expected a variant name or a list tagged with one, found `(Circle . 1.0)`");
+}
+
+#[test]
+fn deserialize_hash_tables() {
+    let map: BTreeMap<u32, Vec<String>> =
+        from_str(r#"#hash((2 . ("b")) (1 . ("a" "c")))"#).unwrap();
+    assert_debug_snapshot!(map, @r#"
+    {
+        1: [
+            "a",
+            "c",
+        ],
+        2: [
+            "b",
+        ],
+    }
+    "#);
+}
+
+#[test]
+fn deserialize_enum() {
+    let shapes: Vec<Shape> = from_str(
+        r"(Empty (Empty) (Circle 1.5) (Rect #x10 2)
+           (Labeled {label : #\c, shape : (Circle 2)}))",
+    )
+    .unwrap();
+    assert_debug_snapshot!(shapes, @r"
+    [
+        Empty,
+        Empty,
+        Circle(
+            1.5,
+        ),
+        Rect(
+            16,
+            2,
+        ),
+        Labeled {
+            label: 'c',
+            shape: Circle(
+                2.0,
+            ),
+        },
+    ]
+    ");
+}
+
+#[test]
+fn quoted_identifiers_are_symbols() {
+    let (value, map): (String, BTreeMap<String, String>) =
+        from_str("(|a b| {|a b| : |c d|})").unwrap();
+    assert_eq!(value, "a b");
+    assert_eq!(map["a b"], "c d");
+    let shape: Shape = from_str("(|Circle| 1.0)").unwrap();
+    assert!(matches!(shape, Shape::Circle(_)));
+}
+
+#[test]
+fn errors_point_at_the_notation() {
+    let errors = [
+        from_str::<Server>(r#"{name : "main", port : 80}"#).unwrap_err(),
+        from_str::<Server>(r#"{name : "main", port : 70000, tags : ()}"#).unwrap_err(),
+        from_str::<Vec<Shape>>("((Rect 1) Empty)").unwrap_err(),
+        from_str::<Shape>("(Square 1)").unwrap_err(),
+        from_str::<Vec<i64>>("(1 2").unwrap_err(),
+    ];
+    let messages: Vec<_> = errors
+        .iter()
+        .map(|err| format!("{} at `{}`", err, err.span.as_ref().unwrap().span))
+        .collect();
+    assert_snapshot!(messages.join("\n"), @r#"
+    1:1: missing field `tags` at `{name : "main", port : 80}`
+    1:24: invalid value: integer `70000`, expected u16 at `70000`
+    1:2: invalid length 1, expected tuple variant Shape::Rect with 2 elements at `(Rect 1)`
+    1:1: unknown variant `Square`, expected one of `Empty`, `Circle`, `Rect`, `Labeled` at `(Square 1)`
+    1:5: unclosed delimiter `(` at ``
+    "#);
+}
+
+#[derive(Debug, Deserialize)]
+struct Listen {
+    host: Spanned<String>,
+    ports: Vec<Spanned<u32>>,
+}
+
+#[test]
+fn spanned_values() {
+    let listen: Listen = from_str(
+        r#"{host : "localhost",
+            ports : #(80 99999)}"#,
+    )
+    .unwrap();
+    let bad = listen
+        .ports
+        .iter()
+        .find(|port| *port.get_ref() > 65535)
+        .unwrap();
+    assert_eq!(listen.host.get_ref(), "localhost");
+    assert_eq!(listen.host.span().span, r#""localhost""#);
+    assert_debug_snapshot!(bad.span(), @r#"
+    DiagnosticSpan {
+        start_line: 2,
+        start_col: 26,
+        start_offset: 46,
+        end_line: 2,
+        end_col: 31,
+        end_offset: 51,
+        span: "99999",
+        file: None,
+    }
+    "#);
+}
+
+#[test]
+fn deserialize_bytes() {
+    let bytes: Vec<u8> = from_str("#u8(1 2 255)").unwrap();
+    assert_eq!(bytes, [1, 2, 255]);
+    let bytes: Vec<u8> = from_str(r#"#"ab\x0;""#).unwrap();
+    assert_eq!(bytes, [b'a', b'b', 0]);
+    let err = from_str::<[u8; 2]>("#u8(1 2 3)").unwrap_err();
+    assert_snapshot!(err, @"1:1: invalid length 3, expected 2 elements in sequence");
+}
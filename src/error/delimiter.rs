@@ -0,0 +1,228 @@
+use std::ops::Range;
+
+/// The first delimiter problem in a source, found without running the grammar.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum DelimiterError {
+    /// `opener` is still open at the end of input.
+    Unclosed {
+        opener: Range<usize>,
+        text: &'static str,
+    },
+    /// `opener` is closed by a closer of another kind.
+    Mismatched {
+        opener: Range<usize>,
+        text: &'static str,
+        closer: Range<usize>,
+        found: &'static str,
+    },
+    /// `closer` has nothing to close.
+    Unexpected {
+        closer: Range<usize>,
+        found: &'static str,
+    },
+    /// The block comment opened by `opener` is still open at the end of input.
+    UnterminatedComment { opener: Range<usize> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Delimiter {
+    Open(&'static str),
+    Close(&'static str),
+}
+
+pub(crate) fn closer_of(opener: &str) -> &'static str {
+    match opener {
+        "(" | "#(" | "#u8(" | "#hash(" | "#hasheqv(" | "#hasheq(" => ")",
+        "[" | "#[" => "]",
+        _ => "}",
+    }
+}
+
+const OPENERS: [&str; 10] = [
+    "#hasheqv(",
+    "#hasheq(",
+    "#hash(",
+    "#u8(",
+    "#(",
+    "#[",
+    "#{",
+    "(",
+    "[",
+    "{",
+];
+const CLOSERS: [&str; 3] = [")", "]", "}"];
+
+/// Iterates over the delimiters of `input`, skipping strings, comments, characters and quoted
+/// identifiers so that their content cannot unbalance anything.
+pub(crate) struct Delimiters<'i> {
+    input: &'i str,
+    pos: usize,
+    /// Set when a string or quoted identifier runs until the end of input.
+    pub(crate) unterminated: bool,
+    /// The `#|` of a block comment that runs until the end of input.
+    pub(crate) unterminated_comment: Option<Range<usize>>,
+}
+
+impl<'i> Delimiters<'i> {
+    pub(crate) fn new(input: &'i str, from: usize) -> Self {
+        Self {
+            input,
+            pos: from,
+            unterminated: false,
+            unterminated_comment: None,
+        }
+    }
+}
+
+impl Iterator for Delimiters<'_> {
+    type Item = (Range<usize>, Delimiter);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.input.as_bytes();
+        while self.pos < bytes.len() {
+            let i = self.pos;
+            let rest = &self.input[i..];
+            if let Some(after) = rest.strip_prefix("#\\") {
+                // skip the character right after `#\`, whatever it is
+                self.pos += 2 + after.chars().next().map_or(0, char::len_utf8);
+            } else if rest.starts_with("#|") {
+                match block_comment_len(rest) {
+                    Some(len) => self.pos += len,
+                    None => {
+                        self.unterminated_comment = Some(i..i + 2);
+                        self.pos = bytes.len();
+                        return None;
+                    }
+                }
+            } else if rest.starts_with(';') {
+                self.pos += rest.find('\n').unwrap_or(rest.len());
+            } else if rest.starts_with('"') || rest.starts_with('|') {
+                let quote = bytes[i];
+                let mut j = i + 1;
+                while j < bytes.len() && bytes[j] != quote {
+                    if bytes[j] == b'\\' && quote == b'"' {
+                        j += 1;
+                    }
+                    j += 1;
+                }
+                if j >= bytes.len() {
+                    self.unterminated = true;
+                    self.pos = bytes.len();
+                    return None;
+                }
+                self.pos = j + 1;
+            } else if let Some(opener) = OPENERS.iter().find(|o| rest.starts_with(**o)) {
+                self.pos += opener.len();
+                return Some((i..self.pos, Delimiter::Open(opener)));
+            } else if let Some(closer) = CLOSERS.iter().find(|c| rest.starts_with(**c)) {
+                self.pos += 1;
+                return Some((i..self.pos, Delimiter::Close(closer)));
+            } else {
+                self.pos += rest.chars().next().map_or(1, char::len_utf8);
+            }
+        }
+        None
+    }
+}
+
+/// The length of the nested block comment at the start of `input`, `None` if it is never
+/// closed.
+pub(crate) fn block_comment_len(input: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut pos = 0;
+    while pos < input.len() {
+        let rest = &input[pos..];
+        if rest.starts_with("#|") {
+            depth += 1;
+            pos += 2;
+        } else if rest.starts_with("|#") {
+            depth -= 1;
+            pos += 2;
+            if depth == 0 {
+                return Some(pos);
+            }
+        } else {
+            pos += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    None
+}
+
+/// The delimiters of a whole input, scanned once so that the recovering parser can check
+/// each broken form without scanning the rest of the input again.
+pub(crate) struct Scan {
+    delimiters: Vec<(Range<usize>, Delimiter)>,
+    unterminated: bool,
+    unterminated_comment: Option<Range<usize>>,
+}
+
+impl Scan {
+    pub(crate) fn new(input: &str) -> Self {
+        let mut iter = Delimiters::new(input, 0);
+        let delimiters = iter.by_ref().collect();
+        Self {
+            delimiters,
+            unterminated: iter.unterminated,
+            unterminated_comment: iter.unterminated_comment,
+        }
+    }
+
+    /// The delimiters from `from` on.
+    pub(crate) fn from(&self, from: usize) -> &[(Range<usize>, Delimiter)] {
+        let first = self
+            .delimiters
+            .partition_point(|(range, _)| range.start < from);
+        &self.delimiters[first..]
+    }
+
+    /// The first delimiter problem of the input from `from` on, with ranges relative to
+    /// `from`. A closer after `until` is not looked at, so only a problem at or before it is
+    /// found, and an unclosed opener only if `until` is the end of input.
+    pub(crate) fn check(&self, from: usize, until: usize) -> Option<DelimiterError> {
+        let at = |range: &Range<usize>| range.start - from..range.end - from;
+        let mut stack: Vec<(Range<usize>, &'static str)> = vec![];
+        for (range, delimiter) in self.from(from) {
+            if range.start > until {
+                return None;
+            }
+            match *delimiter {
+                Delimiter::Open(opener) => stack.push((at(range), opener)),
+                Delimiter::Close(found) => match stack.pop() {
+                    None => {
+                        return Some(DelimiterError::Unexpected {
+                            closer: at(range),
+                            found,
+                        })
+                    }
+                    Some((opener, text)) if closer_of(text) != found => {
+                        return Some(DelimiterError::Mismatched {
+                            opener,
+                            text,
+                            closer: at(range),
+                            found,
+                        })
+                    }
+                    Some(_) => {}
+                },
+            }
+        }
+        match &self.unterminated_comment {
+            Some(opener) if opener.start >= from => {
+                return Some(DelimiterError::UnterminatedComment { opener: at(opener) });
+            }
+            _ => {}
+        }
+        if self.unterminated {
+            // the grammar will report a better error than us
+            return None;
+        }
+        stack
+            .pop()
+            .map(|(opener, text)| DelimiterError::Unclosed { opener, text })
+    }
+}
+
+/// Finds the first delimiter problem of `input`.
+pub(crate) fn check(input: &str) -> Option<DelimiterError> {
+    Scan::new(input).check(0, input.len())
+}
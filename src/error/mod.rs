@@ -0,0 +1,351 @@
+use std::{fmt::Display, io, ops::Range};
+
+use ariadne::{Color, Config, IndexType, Label, Report, ReportKind, Source};
+use delimiter::{closer_of, DelimiterError};
+use pest::error::{ErrorVariant, InputLocation};
+
+use crate::{DiagnosticSpan, Rule};
+
+pub(crate) mod delimiter;
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The input does not match the grammar.
+    Syntax,
+    /// The input matches the grammar, but the literal cannot be converted.
+    InvalidLiteral(String),
+    /// A `#\name` character literal with a name that is not known.
+    UnknownCharName(String),
+    /// An element of a `#u8(...)` bytevector is not an exact integer from 0 to 255.
+    InvalidByte(String),
+    /// An element of a `#hash(...)` table is not a `(key . value)` pair, a labeled pair is
+    /// not one either.
+    InvalidHashEntry(String),
+    /// A key is given twice in a hash table, as the flavor of the table compares keys.
+    DuplicateHashKey(String),
+    /// A rational literal has `0` as denominator.
+    ZeroDenominator,
+    /// The input ends while `opener` is still open.
+    UnclosedDelimiter { opener: &'static str },
+    /// `opener` is closed by `found` instead of its own closer.
+    MismatchedDelimiter {
+        opener: &'static str,
+        found: &'static str,
+    },
+    /// `found` closes nothing.
+    UnexpectedDelimiter { found: &'static str },
+    /// A `#|` block comment is never closed by `|#`.
+    UnterminatedComment,
+    /// A `#n#` refers to a label that is not defined before it, or to the label it is the
+    /// value of, as in `#0=#0#`.
+    UndefinedLabel(u64),
+    /// A `#n=` defines a label that is already defined in the same top-level notation.
+    DuplicateLabel(u64),
+    /// A `.` that does not separate the elements of a list from its last notation, the
+    /// reason tells what is wrong with it.
+    MisplacedDot(&'static str),
+    /// A `#:` keyword without a name.
+    EmptyKeyword,
+    /// A keyword is given twice in the arguments of a form.
+    DuplicateKeyword(String),
+    /// A keyword ends the arguments of a form, or is followed by another keyword.
+    MissingKeywordArgument(String),
+    /// A `#n#` closes a cycle, which a [`Value`](crate::Value) cannot hold.
+    CyclicValue,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub span: DiagnosticSpan,
+    /// The rules pest was trying to match when it failed, empty for non-syntax errors.
+    pub expected: Vec<Rule>,
+    /// A second location involved in the error, e.g. the opener of an unclosed delimiter.
+    pub related: Option<DiagnosticSpan>,
+}
+
+impl ParseError {
+    pub(crate) fn from_pest(input: &str, err: pest::error::Error<Rule>) -> Self {
+        Self::from_pest_with(input, err, |_| delimiter::check(input))
+    }
+
+    /// Like `from_pest`, `check` finds the delimiter problem of `input` given where pest gave
+    /// up.
+    pub(crate) fn from_pest_with(
+        input: &str,
+        err: pest::error::Error<Rule>,
+        check: impl FnOnce(usize) -> Option<DelimiterError>,
+    ) -> Self {
+        let (start, end) = match err.location {
+            InputLocation::Pos(pos) => (pos, pos),
+            InputLocation::Span(span) => span,
+        };
+        let expected = match err.variant {
+            ErrorVariant::ParsingError { positives, .. } => positives,
+            ErrorVariant::CustomError { .. } => vec![],
+        };
+        let mut error = Self {
+            kind: ParseErrorKind::Syntax,
+            span: span_of(input, start, end),
+            expected,
+            related: None,
+        };
+        // pest only knows where it gave up, the delimiters tell which opener is to blame
+        match check(start) {
+            Some(DelimiterError::Unclosed { opener, text }) if start == input.len() => {
+                error.kind = ParseErrorKind::UnclosedDelimiter { opener: text };
+                error.related = Some(span_of(input, opener.start, opener.end));
+            }
+            Some(DelimiterError::Mismatched {
+                opener,
+                text,
+                closer,
+                found,
+            }) if start == closer.start => {
+                error.kind = ParseErrorKind::MismatchedDelimiter {
+                    opener: text,
+                    found,
+                };
+                error.span = span_of(input, closer.start, closer.end);
+                error.related = Some(span_of(input, opener.start, opener.end));
+            }
+            Some(DelimiterError::Unexpected { closer, found }) if start == closer.start => {
+                error.kind = ParseErrorKind::UnexpectedDelimiter { found };
+                error.span = span_of(input, closer.start, closer.end);
+            }
+            // everything after the `#|` is comment, so it is to blame for any later failure
+            Some(DelimiterError::UnterminatedComment { opener }) if start >= opener.start => {
+                error.kind = ParseErrorKind::UnterminatedComment;
+                error.span = span_of(input, opener.start, opener.end);
+                error.expected = vec![];
+            }
+            _ => {}
+        }
+        error
+    }
+
+    pub(crate) fn from_kind(kind: ParseErrorKind, span: pest::Span<'_>) -> Self {
+        Self {
+            kind,
+            span: DiagnosticSpan::from_pest_span(span),
+            expected: vec![],
+            related: None,
+        }
+    }
+
+    /// Builds a colored diagnostic for this error, `source` must be the parsed input.
+    pub fn report(&self, source: &str) -> Report<'static, (String, Range<usize>)> {
+        let file = self
+            .span
+            .file
+            .clone()
+            .unwrap_or_else(|| "<input>".to_string());
+        let span = |s: &DiagnosticSpan| (file.clone(), visible_range(source, s));
+        let primary = span(&self.span);
+        let mut report = Report::build(ReportKind::Error, primary.clone())
+            .with_config(Config::default().with_index_type(IndexType::Byte))
+            .with_message(&self.kind);
+        let mut label = Label::new(primary).with_color(Color::Red);
+        match &self.kind {
+            ParseErrorKind::Syntax => {
+                if let Some(expected) = self.expected_message() {
+                    label = label.with_message(format!("expected {}", expected));
+                }
+            }
+            ParseErrorKind::UnclosedDelimiter { opener } => {
+                label = label.with_message(format!("expected `{}` here", closer_of(opener)));
+            }
+            ParseErrorKind::MismatchedDelimiter { opener, .. } => {
+                label = label.with_message(format!("expected `{}` here", closer_of(opener)));
+            }
+            ParseErrorKind::UnexpectedDelimiter { found } => {
+                label = label.with_message(format!("`{}` closes nothing", found));
+            }
+            ParseErrorKind::UnterminatedComment => {
+                label = label.with_message("this comment needs a closing `|#`");
+            }
+            kind => label = label.with_message(kind),
+        }
+        report = report.with_label(label);
+        if let Some(related) = &self.related {
+            let message = match &self.kind {
+                ParseErrorKind::MismatchedDelimiter { opener, found } => {
+                    format!("`{}` opened here is closed by `{}`", opener, found)
+                }
+                ParseErrorKind::UnclosedDelimiter { opener } => {
+                    format!("`{}` opened here is never closed", opener)
+                }
+                ParseErrorKind::DuplicateLabel(label) => {
+                    format!("`#{}=` is first defined here", label)
+                }
+                ParseErrorKind::DuplicateKeyword(name) => {
+                    format!("`#:{}` is first given here", name)
+                }
+                ParseErrorKind::DuplicateHashKey(key) => {
+                    format!("`{}` is first given here", key)
+                }
+                _ => "related to this".to_string(),
+            };
+            report = report.with_label(
+                Label::new(span(related))
+                    .with_color(Color::Blue)
+                    .with_message(message),
+            );
+        }
+        report.finish()
+    }
+
+    /// Renders the report of this error into `w`.
+    pub fn write_to<W: io::Write>(&self, source: &str, w: W) -> io::Result<()> {
+        let file = self
+            .span
+            .file
+            .clone()
+            .unwrap_or_else(|| "<input>".to_string());
+        self.report(source).write((file, Source::from(source)), w)
+    }
+
+    fn expected_message(&self) -> Option<String> {
+        let mut expected: Vec<&str> = vec![];
+        for name in self.expected.iter().map(describe) {
+            if !expected.contains(&name) {
+                expected.push(name);
+            }
+        }
+        if expected.is_empty() {
+            None
+        } else {
+            Some(expected.join(" or "))
+        }
+    }
+}
+
+/// Empty spans (e.g. end of input) still need one column to point at, at the end of input
+/// it is the last character.
+fn visible_range(source: &str, span: &DiagnosticSpan) -> Range<usize> {
+    let start = span.start_offset;
+    if start < span.end_offset {
+        return start..span.end_offset;
+    }
+    match source[start..].chars().next() {
+        Some(c) => start..start + c.len_utf8(),
+        None => match source[..start].chars().next_back() {
+            Some(c) => start - c.len_utf8()..start,
+            None => start..start,
+        },
+    }
+}
+
+pub(crate) fn span_of(input: &str, start: usize, end: usize) -> DiagnosticSpan {
+    DiagnosticSpan::from_pest_span(
+        pest::Span::new(input, start, end).expect("pest reports positions inside its input"),
+    )
+}
+
+fn describe(rule: &Rule) -> &'static str {
+    match rule {
+        Rule::EOI => "end of input",
+        Rule::boolean => "boolean",
+        Rule::char => "character",
+        Rule::int => "integer",
+        Rule::rational => "rational",
+        Rule::float => "float",
+        Rule::complex => "complex number",
+        Rule::string => "string",
+        Rule::byte_string => "byte string",
+        Rule::identifier => "identifier",
+        Rule::keyword => "keyword",
+        Rule::list | Rule::paren_list | Rule::bracket_list => "list",
+        Rule::dot | Rule::dotted_tail => "dotted tail",
+        Rule::vector | Rule::paren_vector | Rule::bracket_vector => "vector",
+        Rule::set => "set",
+        Rule::bytevector => "bytevector",
+        Rule::hash | Rule::hash_open => "hash table",
+        Rule::object => "object",
+        Rule::object_pair => "object pair",
+        Rule::literal => "literal",
+        Rule::container => "container",
+        Rule::quoting | Rule::quote | Rule::quasiquote | Rule::unquote | Rule::unquote_splicing => {
+            "quoting"
+        }
+        Rule::syntaxing
+        | Rule::syntax
+        | Rule::quasisyntax
+        | Rule::unsyntax
+        | Rule::unsyntax_splicing => "syntaxing",
+        Rule::labeling | Rule::label | Rule::label_def => "label",
+        Rule::label_ref => "label reference",
+        _ => "notation",
+    }
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::Syntax => write!(f, "syntax error"),
+            ParseErrorKind::InvalidLiteral(reason) => write!(f, "invalid literal: {}", reason),
+            ParseErrorKind::UnknownCharName(name) => {
+                write!(f, "unknown character name `#\\{}`", name)
+            }
+            ParseErrorKind::InvalidByte(elem) => {
+                write!(
+                    f,
+                    "`{}` is not a byte, expected an integer from 0 to 255",
+                    elem
+                )
+            }
+            ParseErrorKind::InvalidHashEntry(elem) => {
+                write!(f, "`{}` is not a `(key . value)` pair", elem)
+            }
+            ParseErrorKind::DuplicateHashKey(key) => {
+                write!(f, "duplicate key `{}` in hash table", key)
+            }
+            ParseErrorKind::ZeroDenominator => write!(f, "rational literal has a zero denominator"),
+            ParseErrorKind::UnclosedDelimiter { opener } => {
+                write!(f, "unclosed delimiter `{}`", opener)
+            }
+            ParseErrorKind::MismatchedDelimiter { opener, found } => write!(
+                f,
+                "mismatched closing delimiter `{}` for `{}`",
+                found, opener
+            ),
+            ParseErrorKind::UnexpectedDelimiter { found } => {
+                write!(f, "unexpected closing delimiter `{}`", found)
+            }
+            ParseErrorKind::UnterminatedComment => write!(f, "unterminated block comment"),
+            ParseErrorKind::UndefinedLabel(label) => write!(f, "undefined label `#{}#`", label),
+            ParseErrorKind::DuplicateLabel(label) => write!(f, "duplicate label `#{}=`", label),
+            ParseErrorKind::MisplacedDot(reason) => write!(f, "misplaced `.`, {}", reason),
+            ParseErrorKind::EmptyKeyword => write!(f, "keyword `#:` has no name"),
+            ParseErrorKind::DuplicateKeyword(name) => write!(f, "duplicate keyword `#:{}`", name),
+            ParseErrorKind::MissingKeywordArgument(name) => {
+                write!(f, "missing argument after keyword `#:{}`", name)
+            }
+            ParseErrorKind::CyclicValue => write!(f, "a value cannot be cyclic"),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(file) = &self.span.file {
+            write!(f, "{}:", file)?;
+        }
+        write!(
+            f,
+            "{}:{}: {}",
+            self.span.start_line, self.span.start_col, self.kind
+        )?;
+        match self.expected_message() {
+            Some(expected) if self.kind == ParseErrorKind::Syntax => {
+                write!(f, ", expected {}", expected)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
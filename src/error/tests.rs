@@ -0,0 +1,89 @@
+use insta::assert_snapshot;
+
+use crate::{parse_file, parse_notation, ParseErrorKind};
+
+#[test]
+fn syntax_error() {
+    let err = parse_file("{a 1}").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::Syntax);
+    assert_snapshot!(err, @"1:2: syntax error, expected object pair");
+    assert_snapshot!(parse_notation("1 2").unwrap_err(), @"1:3: syntax error, expected end of input");
+}
+
+#[test]
+fn literal_error() {
+    let err = parse_file("(1 -3/0)").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::ZeroDenominator);
+    assert_snapshot!(err.span.span, @"-3/0");
+    assert_snapshot!(err, @"1:4: rational literal has a zero denominator");
+    assert_snapshot!(parse_notation("-9223372036854775808").unwrap(), @"-9223372036854775808");
+}
+
+fn render(source: &str) -> String {
+    let mut out = vec![];
+    parse_file(source)
+        .unwrap_err()
+        .write_to(source, &mut out)
+        .unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn delimiter_error() {
+    assert_snapshot!(parse_file("(define x\n  (f 1)").unwrap_err(), @"2:8: unclosed delimiter `(`");
+    assert_snapshot!(parse_file("(f #[1 2)").unwrap_err(), @"1:9: mismatched closing delimiter `)` for `#[`");
+    assert_snapshot!(parse_file("(f 1))").unwrap_err(), @"1:6: unexpected closing delimiter `)`");
+    // delimiters inside strings and characters do not count
+    assert_snapshot!(parse_file("(#\\( \"abc\"").unwrap_err(), @"1:11: unclosed delimiter `(`");
+}
+
+#[test]
+fn report_error() {
+    assert_snapshot!(render("(define x\n  (f 1]"), @r"
+    Error: mismatched closing delimiter `]` for `(`
+       ╭─[<input>:2:7]
+       │
+     2 │   (f 1]
+       │   ┬   ┬  
+       │   ╰────── `(` opened here is closed by `]`
+       │       │  
+       │       ╰── expected `)` here
+    ───╯
+    ");
+    assert_snapshot!(render("(f 1\n"), @r"
+    Error: unclosed delimiter `(`
+       ╭─[<input>:1:5]
+       │
+     1 │ (f 1
+       │ ┬   ┬  
+       │ ╰────── `(` opened here is never closed
+       │     │  
+       │     ╰── expected `)` here
+    ───╯
+    ");
+    assert_snapshot!(render("{a 1}"), @r"
+    Error: syntax error
+       ╭─[<input>:1:2]
+       │
+     1 │ {a 1}
+       │  ┬  
+       │  ╰── expected object pair
+    ───╯
+    ");
+}
+
+#[test]
+fn unterminated_comment() {
+    let err = parse_file("(a #| b #| c |#\n(d)").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnterminatedComment);
+    assert_snapshot!(err, @"1:4: unterminated block comment");
+    assert_snapshot!(render("1\n#| 2\n3"), @r"
+    Error: unterminated block comment
+       ╭─[<input>:2:1]
+       │
+     2 │ #| 2
+       │ ─┬  
+       │  ╰── this comment needs a closing `|#`
+    ───╯
+    ");
+}
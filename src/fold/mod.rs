@@ -0,0 +1,494 @@
+//! Rebuilds a tree by value.
+//!
+//! Every method of [`Fold`] rebuilds its node from the folded children through the function
+//! of the same name in this module, a pass overrides the methods of the nodes it replaces.
+//! Unlike [`VisitMut`](crate::visit_mut::VisitMut) the children are owned, so a pass can move
+//! them into a new node without cloning.
+
+use crate::{
+    container::{
+        bytevector::Bytevector,
+        hash::{HashEntry, HashTable},
+        list::{BList, List, PList},
+        object::{Object, ObjectPair},
+        set::Set,
+        vector::{BVector, PVector, Vector},
+        Container,
+    },
+    labeling::{Label, LabelRef, Labeling},
+    literal::{
+        Boolean, ByteString, Char, Complex, Float, Identifier, Integer, Keyword, Literal, Rational,
+        String_,
+    },
+    quoting::{QuasiQuote, Quote, Quoting, Unquote, UnquoteSplicing},
+    syntaxing::{QuasiSyntax, Syntax, Syntaxing, Unsyntax, UnsyntaxSplicing},
+    DiagnosticSpan, EFile, ENotation, ENotationBody, ErrorNode,
+};
+
+pub trait Fold {
+    fn fold_file(&mut self, node: EFile) -> EFile {
+        fold_file(self, node)
+    }
+
+    fn fold_notation(&mut self, node: ENotation) -> ENotation {
+        fold_notation(self, node)
+    }
+
+    fn fold_span(&mut self, node: DiagnosticSpan) -> DiagnosticSpan {
+        fold_span(self, node)
+    }
+
+    fn fold_body(&mut self, node: ENotationBody) -> ENotationBody {
+        fold_body(self, node)
+    }
+
+    fn fold_error_node(&mut self, node: ErrorNode) -> ErrorNode {
+        fold_error_node(self, node)
+    }
+
+    fn fold_literal(&mut self, node: Literal) -> Literal {
+        fold_literal(self, node)
+    }
+
+    fn fold_boolean(&mut self, node: Boolean) -> Boolean {
+        fold_boolean(self, node)
+    }
+
+    fn fold_char(&mut self, node: Char) -> Char {
+        fold_char(self, node)
+    }
+
+    fn fold_float(&mut self, node: Float) -> Float {
+        fold_float(self, node)
+    }
+
+    fn fold_rational(&mut self, node: Rational) -> Rational {
+        fold_rational(self, node)
+    }
+
+    fn fold_integer(&mut self, node: Integer) -> Integer {
+        fold_integer(self, node)
+    }
+
+    fn fold_complex(&mut self, node: Complex) -> Complex {
+        fold_complex(self, node)
+    }
+
+    fn fold_string(&mut self, node: String_) -> String_ {
+        fold_string(self, node)
+    }
+
+    fn fold_identifier(&mut self, node: Identifier) -> Identifier {
+        fold_identifier(self, node)
+    }
+
+    fn fold_keyword(&mut self, node: Keyword) -> Keyword {
+        fold_keyword(self, node)
+    }
+
+    fn fold_byte_string(&mut self, node: ByteString) -> ByteString {
+        fold_byte_string(self, node)
+    }
+
+    fn fold_container(&mut self, node: Container) -> Container {
+        fold_container(self, node)
+    }
+
+    fn fold_list(&mut self, node: List) -> List {
+        fold_list(self, node)
+    }
+
+    fn fold_plist(&mut self, node: PList) -> PList {
+        fold_plist(self, node)
+    }
+
+    fn fold_blist(&mut self, node: BList) -> BList {
+        fold_blist(self, node)
+    }
+
+    fn fold_vector(&mut self, node: Vector) -> Vector {
+        fold_vector(self, node)
+    }
+
+    fn fold_pvector(&mut self, node: PVector) -> PVector {
+        fold_pvector(self, node)
+    }
+
+    fn fold_bvector(&mut self, node: BVector) -> BVector {
+        fold_bvector(self, node)
+    }
+
+    fn fold_set(&mut self, node: Set) -> Set {
+        fold_set(self, node)
+    }
+
+    fn fold_object(&mut self, node: Object) -> Object {
+        fold_object(self, node)
+    }
+
+    fn fold_object_pair(&mut self, node: ObjectPair) -> ObjectPair {
+        fold_object_pair(self, node)
+    }
+
+    fn fold_bytevector(&mut self, node: Bytevector) -> Bytevector {
+        fold_bytevector(self, node)
+    }
+
+    fn fold_hash_table(&mut self, node: HashTable) -> HashTable {
+        fold_hash_table(self, node)
+    }
+
+    fn fold_hash_entry(&mut self, node: HashEntry) -> HashEntry {
+        fold_hash_entry(self, node)
+    }
+
+    fn fold_quoting(&mut self, node: Quoting) -> Quoting {
+        fold_quoting(self, node)
+    }
+
+    fn fold_quote(&mut self, node: Quote) -> Quote {
+        fold_quote(self, node)
+    }
+
+    fn fold_quasi_quote(&mut self, node: QuasiQuote) -> QuasiQuote {
+        fold_quasi_quote(self, node)
+    }
+
+    fn fold_unquote(&mut self, node: Unquote) -> Unquote {
+        fold_unquote(self, node)
+    }
+
+    fn fold_unquote_splicing(&mut self, node: UnquoteSplicing) -> UnquoteSplicing {
+        fold_unquote_splicing(self, node)
+    }
+
+    fn fold_syntaxing(&mut self, node: Syntaxing) -> Syntaxing {
+        fold_syntaxing(self, node)
+    }
+
+    fn fold_syntax(&mut self, node: Syntax) -> Syntax {
+        fold_syntax(self, node)
+    }
+
+    fn fold_quasi_syntax(&mut self, node: QuasiSyntax) -> QuasiSyntax {
+        fold_quasi_syntax(self, node)
+    }
+
+    fn fold_unsyntax(&mut self, node: Unsyntax) -> Unsyntax {
+        fold_unsyntax(self, node)
+    }
+
+    fn fold_unsyntax_splicing(&mut self, node: UnsyntaxSplicing) -> UnsyntaxSplicing {
+        fold_unsyntax_splicing(self, node)
+    }
+
+    fn fold_labeling(&mut self, node: Labeling) -> Labeling {
+        fold_labeling(self, node)
+    }
+
+    fn fold_label(&mut self, node: Label) -> Label {
+        fold_label(self, node)
+    }
+
+    fn fold_label_ref(&mut self, node: LabelRef) -> LabelRef {
+        fold_label_ref(self, node)
+    }
+}
+
+pub fn fold_file<F: Fold + ?Sized>(f: &mut F, node: EFile) -> EFile {
+    EFile {
+        notations: node
+            .notations
+            .into_iter()
+            .map(|elem| f.fold_notation(elem))
+            .collect(),
+        _eoi: node._eoi,
+    }
+}
+
+pub fn fold_notation<F: Fold + ?Sized>(f: &mut F, node: ENotation) -> ENotation {
+    ENotation {
+        span: f.fold_span(node.span),
+        body: f.fold_body(node.body),
+    }
+}
+
+pub fn fold_span<F: Fold + ?Sized>(_f: &mut F, node: DiagnosticSpan) -> DiagnosticSpan {
+    node
+}
+
+pub fn fold_body<F: Fold + ?Sized>(f: &mut F, node: ENotationBody) -> ENotationBody {
+    match node {
+        ENotationBody::Literal(x) => ENotationBody::Literal(f.fold_literal(x)),
+        ENotationBody::Container(x) => ENotationBody::Container(f.fold_container(x)),
+        ENotationBody::Quoting(x) => ENotationBody::Quoting(f.fold_quoting(x)),
+        ENotationBody::Syntaxing(x) => ENotationBody::Syntaxing(f.fold_syntaxing(x)),
+        ENotationBody::Labeling(x) => ENotationBody::Labeling(f.fold_labeling(x)),
+        ENotationBody::Error(x) => ENotationBody::Error(f.fold_error_node(x)),
+    }
+}
+
+pub fn fold_error_node<F: Fold + ?Sized>(_f: &mut F, node: ErrorNode) -> ErrorNode {
+    node
+}
+
+pub fn fold_literal<F: Fold + ?Sized>(f: &mut F, node: Literal) -> Literal {
+    match node {
+        Literal::Boolean(x) => Literal::Boolean(f.fold_boolean(x)),
+        Literal::Char(x) => Literal::Char(f.fold_char(x)),
+        Literal::Float(x) => Literal::Float(f.fold_float(x)),
+        Literal::Rational(x) => Literal::Rational(f.fold_rational(x)),
+        Literal::Int(x) => Literal::Int(f.fold_integer(x)),
+        Literal::Complex(x) => Literal::Complex(f.fold_complex(x)),
+        Literal::String_(x) => Literal::String_(f.fold_string(x)),
+        Literal::Identifier(x) => Literal::Identifier(f.fold_identifier(x)),
+        Literal::Keyword(x) => Literal::Keyword(f.fold_keyword(x)),
+        Literal::ByteString(x) => Literal::ByteString(f.fold_byte_string(x)),
+    }
+}
+
+pub fn fold_boolean<F: Fold + ?Sized>(_f: &mut F, node: Boolean) -> Boolean {
+    node
+}
+
+pub fn fold_char<F: Fold + ?Sized>(_f: &mut F, node: Char) -> Char {
+    node
+}
+
+pub fn fold_float<F: Fold + ?Sized>(_f: &mut F, node: Float) -> Float {
+    node
+}
+
+pub fn fold_rational<F: Fold + ?Sized>(_f: &mut F, node: Rational) -> Rational {
+    node
+}
+
+pub fn fold_integer<F: Fold + ?Sized>(_f: &mut F, node: Integer) -> Integer {
+    node
+}
+
+pub fn fold_complex<F: Fold + ?Sized>(_f: &mut F, node: Complex) -> Complex {
+    node
+}
+
+pub fn fold_string<F: Fold + ?Sized>(_f: &mut F, node: String_) -> String_ {
+    node
+}
+
+pub fn fold_identifier<F: Fold + ?Sized>(_f: &mut F, node: Identifier) -> Identifier {
+    node
+}
+
+pub fn fold_keyword<F: Fold + ?Sized>(_f: &mut F, node: Keyword) -> Keyword {
+    node
+}
+
+pub fn fold_byte_string<F: Fold + ?Sized>(_f: &mut F, node: ByteString) -> ByteString {
+    node
+}
+
+pub fn fold_container<F: Fold + ?Sized>(f: &mut F, node: Container) -> Container {
+    match node {
+        Container::List(x) => Container::List(f.fold_list(x)),
+        Container::Vector(x) => Container::Vector(f.fold_vector(x)),
+        Container::Set(x) => Container::Set(f.fold_set(x)),
+        Container::Object(x) => Container::Object(f.fold_object(x)),
+        Container::Bytevector(x) => Container::Bytevector(f.fold_bytevector(x)),
+        Container::Hash(x) => Container::Hash(f.fold_hash_table(x)),
+    }
+}
+
+pub fn fold_list<F: Fold + ?Sized>(f: &mut F, node: List) -> List {
+    match node {
+        List::PL(x) => List::PL(f.fold_plist(x)),
+        List::BL(x) => List::BL(f.fold_blist(x)),
+    }
+}
+
+pub fn fold_plist<F: Fold + ?Sized>(f: &mut F, node: PList) -> PList {
+    PList {
+        elems: node
+            .elems
+            .into_iter()
+            .map(|elem| f.fold_notation(elem))
+            .collect(),
+        tail: node.tail.map(|tail| Box::new(f.fold_notation(*tail))),
+    }
+}
+
+pub fn fold_blist<F: Fold + ?Sized>(f: &mut F, node: BList) -> BList {
+    BList {
+        elems: node
+            .elems
+            .into_iter()
+            .map(|elem| f.fold_notation(elem))
+            .collect(),
+        tail: node.tail.map(|tail| Box::new(f.fold_notation(*tail))),
+    }
+}
+
+pub fn fold_vector<F: Fold + ?Sized>(f: &mut F, node: Vector) -> Vector {
+    match node {
+        Vector::PV(x) => Vector::PV(f.fold_pvector(x)),
+        Vector::BV(x) => Vector::BV(f.fold_bvector(x)),
+    }
+}
+
+pub fn fold_pvector<F: Fold + ?Sized>(f: &mut F, node: PVector) -> PVector {
+    PVector {
+        elems: node
+            .elems
+            .into_iter()
+            .map(|elem| f.fold_notation(elem))
+            .collect(),
+    }
+}
+
+pub fn fold_bvector<F: Fold + ?Sized>(f: &mut F, node: BVector) -> BVector {
+    BVector {
+        elems: node
+            .elems
+            .into_iter()
+            .map(|elem| f.fold_notation(elem))
+            .collect(),
+    }
+}
+
+pub fn fold_set<F: Fold + ?Sized>(f: &mut F, node: Set) -> Set {
+    Set {
+        elems: node
+            .elems
+            .into_iter()
+            .map(|elem| f.fold_notation(elem))
+            .collect(),
+    }
+}
+
+pub fn fold_object<F: Fold + ?Sized>(f: &mut F, node: Object) -> Object {
+    Object {
+        pairs: node
+            .pairs
+            .into_iter()
+            .map(|elem| f.fold_object_pair(elem))
+            .collect(),
+    }
+}
+
+pub fn fold_object_pair<F: Fold + ?Sized>(f: &mut F, node: ObjectPair) -> ObjectPair {
+    ObjectPair {
+        key: f.fold_identifier(node.key),
+        value: f.fold_notation(node.value),
+    }
+}
+
+pub fn fold_bytevector<F: Fold + ?Sized>(_f: &mut F, node: Bytevector) -> Bytevector {
+    node
+}
+
+pub fn fold_hash_table<F: Fold + ?Sized>(f: &mut F, node: HashTable) -> HashTable {
+    HashTable {
+        flavor: node.flavor,
+        entries: node
+            .entries
+            .into_iter()
+            .map(|elem| f.fold_hash_entry(elem))
+            .collect(),
+    }
+}
+
+pub fn fold_hash_entry<F: Fold + ?Sized>(f: &mut F, node: HashEntry) -> HashEntry {
+    HashEntry {
+        key: f.fold_notation(node.key),
+        value: f.fold_notation(node.value),
+    }
+}
+
+pub fn fold_quoting<F: Fold + ?Sized>(f: &mut F, node: Quoting) -> Quoting {
+    match node {
+        Quoting::Quote(x) => Quoting::Quote(f.fold_quote(x)),
+        Quoting::QuasiQuote(x) => Quoting::QuasiQuote(f.fold_quasi_quote(x)),
+        Quoting::Unquote(x) => Quoting::Unquote(f.fold_unquote(x)),
+        Quoting::UnquoteSplicing(x) => Quoting::UnquoteSplicing(f.fold_unquote_splicing(x)),
+    }
+}
+
+pub fn fold_quote<F: Fold + ?Sized>(f: &mut F, node: Quote) -> Quote {
+    Quote {
+        value: Box::new(f.fold_notation(*node.value)),
+    }
+}
+
+pub fn fold_quasi_quote<F: Fold + ?Sized>(f: &mut F, node: QuasiQuote) -> QuasiQuote {
+    QuasiQuote {
+        value: Box::new(f.fold_notation(*node.value)),
+    }
+}
+
+pub fn fold_unquote<F: Fold + ?Sized>(f: &mut F, node: Unquote) -> Unquote {
+    Unquote {
+        value: Box::new(f.fold_notation(*node.value)),
+    }
+}
+
+pub fn fold_unquote_splicing<F: Fold + ?Sized>(
+    f: &mut F,
+    node: UnquoteSplicing,
+) -> UnquoteSplicing {
+    UnquoteSplicing {
+        value: Box::new(f.fold_notation(*node.value)),
+    }
+}
+
+pub fn fold_syntaxing<F: Fold + ?Sized>(f: &mut F, node: Syntaxing) -> Syntaxing {
+    match node {
+        Syntaxing::Syntax(x) => Syntaxing::Syntax(f.fold_syntax(x)),
+        Syntaxing::QuasiSyntax(x) => Syntaxing::QuasiSyntax(f.fold_quasi_syntax(x)),
+        Syntaxing::Unsyntax(x) => Syntaxing::Unsyntax(f.fold_unsyntax(x)),
+        Syntaxing::UnsyntaxSplicing(x) => Syntaxing::UnsyntaxSplicing(f.fold_unsyntax_splicing(x)),
+    }
+}
+
+pub fn fold_syntax<F: Fold + ?Sized>(f: &mut F, node: Syntax) -> Syntax {
+    Syntax {
+        value: Box::new(f.fold_notation(*node.value)),
+    }
+}
+
+pub fn fold_quasi_syntax<F: Fold + ?Sized>(f: &mut F, node: QuasiSyntax) -> QuasiSyntax {
+    QuasiSyntax {
+        value: Box::new(f.fold_notation(*node.value)),
+    }
+}
+
+pub fn fold_unsyntax<F: Fold + ?Sized>(f: &mut F, node: Unsyntax) -> Unsyntax {
+    Unsyntax {
+        value: Box::new(f.fold_notation(*node.value)),
+    }
+}
+
+pub fn fold_unsyntax_splicing<F: Fold + ?Sized>(
+    f: &mut F,
+    node: UnsyntaxSplicing,
+) -> UnsyntaxSplicing {
+    UnsyntaxSplicing {
+        value: Box::new(f.fold_notation(*node.value)),
+    }
+}
+
+pub fn fold_labeling<F: Fold + ?Sized>(f: &mut F, node: Labeling) -> Labeling {
+    match node {
+        Labeling::Label(x) => Labeling::Label(f.fold_label(x)),
+        Labeling::LabelRef(x) => Labeling::LabelRef(f.fold_label_ref(x)),
+    }
+}
+
+pub fn fold_label<F: Fold + ?Sized>(f: &mut F, node: Label) -> Label {
+    Label {
+        label: node.label,
+        value: Box::new(f.fold_notation(*node.value)),
+    }
+}
+
+pub fn fold_label_ref<F: Fold + ?Sized>(_f: &mut F, node: LabelRef) -> LabelRef {
+    node
+}
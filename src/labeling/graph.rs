@@ -0,0 +1,310 @@
+use std::{
+    collections::HashMap,
+    ops::{Index, IndexMut},
+};
+
+use super::{Label, LabelRef, Labeling};
+use crate::{
+    container::{
+        hash::{HashEntry, HashFlavor, HashTable},
+        list::{List, PList},
+        object::{Object, ObjectPair},
+        set::Set,
+        vector::{PVector, Vector},
+        Container,
+    },
+    value::{identifier, QuoteKind, WriteError},
+    DiagnosticSpan, ENotation, ENotationBody, Value,
+};
+
+/// A node of a [`Graph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+/// What a node of a [`Graph`] holds, containers hold the ids of their elements.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    /// The value of a literal or bytevector, which holds no notations.
+    Atom(Value),
+    List(Vec<NodeId>),
+    /// A list with a dotted tail, which stays as written even if it is a list.
+    DottedList(Vec<NodeId>, NodeId),
+    Vector(Vec<NodeId>),
+    Set(Vec<NodeId>),
+    Map(Vec<(String, NodeId)>),
+    /// The keys and values of a hash table.
+    Hash(HashFlavor, Vec<(NodeId, NodeId)>),
+    Quoted(QuoteKind, NodeId),
+}
+
+impl Node {
+    pub fn children(&self) -> Vec<NodeId> {
+        match self {
+            Node::Atom(_) => vec![],
+            Node::List(elems) | Node::Vector(elems) | Node::Set(elems) => elems.clone(),
+            Node::DottedList(elems, tail) => elems.iter().chain([tail]).copied().collect(),
+            Node::Map(pairs) => pairs.iter().map(|(_, value)| *value).collect(),
+            Node::Hash(_, entries) => entries
+                .iter()
+                .flat_map(|(key, value)| [*key, *value])
+                .collect(),
+            Node::Quoted(_, value) => vec![*value],
+        }
+    }
+}
+
+/// Notations with their labels resolved: a `#n#` is the very node its `#n=` labels, so
+/// shared structure stays shared and a cycle is a cycle.
+///
+/// ```
+/// use enotation::{labeling::{Graph, Node}, parse_notation};
+///
+/// let mut graph = Graph::new();
+/// let root = graph.insert(&parse_notation("#0=(a #0#)").unwrap()).unwrap();
+/// assert_eq!(graph[root].children()[1], root);
+/// assert_eq!(graph.to_notation(root).unwrap().to_string(), "#0=(a #0#)");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    nodes: Vec<Node>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, node: Node) -> NodeId {
+        self.nodes.push(node);
+        NodeId(self.nodes.len() - 1)
+    }
+
+    /// Adds a notation and returns its node, the labels of each notation are its own.
+    ///
+    /// Fails with the span of an error node of the recovering parser, or of a reference to a
+    /// label that is not defined, which the parser already rejects.
+    pub fn insert(&mut self, notation: &ENotation) -> Result<NodeId, DiagnosticSpan> {
+        Resolver {
+            graph: self,
+            labels: HashMap::new(),
+        }
+        .node(notation, None)
+    }
+
+    /// Writes the node as a notation, every node that is reached twice, because it is shared
+    /// or in a cycle, is labeled where it is written first and referred to afterwards.
+    pub fn to_notation(&self, root: NodeId) -> Result<ENotation, WriteError> {
+        let mut writer = Writer {
+            graph: self,
+            reached: HashMap::new(),
+            labels: HashMap::new(),
+        };
+        writer.reach(root);
+        writer.notation(root)
+    }
+}
+
+impl Index<NodeId> for Graph {
+    type Output = Node;
+
+    fn index(&self, id: NodeId) -> &Node {
+        &self.nodes[id.0]
+    }
+}
+
+impl IndexMut<NodeId> for Graph {
+    fn index_mut(&mut self, id: NodeId) -> &mut Node {
+        &mut self.nodes[id.0]
+    }
+}
+
+struct Resolver<'g> {
+    graph: &'g mut Graph,
+    labels: HashMap<u64, NodeId>,
+}
+
+impl Resolver<'_> {
+    /// Builds the node of `notation` into `slot`, a node reserved for a label before its
+    /// value is read, so references inside the value already point at it.
+    fn node(
+        &mut self,
+        notation: &ENotation,
+        slot: Option<NodeId>,
+    ) -> Result<NodeId, DiagnosticSpan> {
+        let all = |this: &mut Self, elems: &[ENotation]| {
+            elems
+                .iter()
+                .map(|elem| this.node(elem, None))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let node = match &notation.body {
+            // `#0=#1#` names the node of `#1#` again
+            ENotationBody::Labeling(Labeling::Label(Label { label, value }))
+                if matches!(value.body, ENotationBody::Labeling(Labeling::LabelRef(_))) =>
+            {
+                let id = self.node(value, None)?;
+                self.labels.insert(*label, id);
+                return Ok(id);
+            }
+            ENotationBody::Labeling(Labeling::Label(Label { label, value })) => {
+                let slot = slot.unwrap_or_else(|| self.graph.push(Node::Atom(Value::nil())));
+                self.labels.insert(*label, slot);
+                return self.node(value, Some(slot));
+            }
+            ENotationBody::Labeling(Labeling::LabelRef(LabelRef { label })) => {
+                return self
+                    .labels
+                    .get(label)
+                    .copied()
+                    .ok_or_else(|| notation.span.clone());
+            }
+            ENotationBody::Literal(_) | ENotationBody::Container(Container::Bytevector(_)) => {
+                Node::Atom(Value::try_from(notation)?)
+            }
+            ENotationBody::Container(container) => match container {
+                Container::List(l) => {
+                    let elems = all(self, l.elems())?;
+                    match l.tail() {
+                        Some(tail) => Node::DottedList(elems, self.node(tail, None)?),
+                        None => Node::List(elems),
+                    }
+                }
+                Container::Vector(v) => Node::Vector(all(self, v.elems())?),
+                Container::Set(s) => Node::Set(all(self, &s.elems)?),
+                Container::Object(o) => Node::Map(
+                    o.pairs
+                        .iter()
+                        .map(|pair| {
+                            Ok((pair.key.symbol().to_string(), self.node(&pair.value, None)?))
+                        })
+                        .collect::<Result<_, _>>()?,
+                ),
+                Container::Hash(h) => Node::Hash(
+                    h.flavor,
+                    h.entries
+                        .iter()
+                        .map(|entry| {
+                            Ok((self.node(&entry.key, None)?, self.node(&entry.value, None)?))
+                        })
+                        .collect::<Result<_, _>>()?,
+                ),
+                Container::Bytevector(_) => unreachable!("bytevectors are atoms"),
+            },
+            ENotationBody::Quoting(q) => {
+                let (kind, value) = QuoteKind::of_quoting(q);
+                Node::Quoted(kind, self.node(value, None)?)
+            }
+            ENotationBody::Syntaxing(s) => {
+                let (kind, value) = QuoteKind::of_syntaxing(s);
+                Node::Quoted(kind, self.node(value, None)?)
+            }
+            ENotationBody::Error(_) => return Err(notation.span.clone()),
+        };
+        Ok(match slot {
+            Some(slot) => {
+                self.graph[slot] = node;
+                slot
+            }
+            None => self.graph.push(node),
+        })
+    }
+}
+
+struct Writer<'g> {
+    graph: &'g Graph,
+    /// How often each node is reached from the root.
+    reached: HashMap<NodeId, usize>,
+    labels: HashMap<NodeId, u64>,
+}
+
+impl Writer<'_> {
+    fn reach(&mut self, id: NodeId) {
+        let reached = self.reached.entry(id).or_default();
+        *reached += 1;
+        if *reached == 1 {
+            for child in self.graph[id].children() {
+                self.reach(child);
+            }
+        }
+    }
+
+    fn notation(&mut self, id: NodeId) -> Result<ENotation, WriteError> {
+        if let Some(label) = self.labels.get(&id) {
+            return Ok(notation(ENotationBody::Labeling(Labeling::LabelRef(
+                LabelRef { label: *label },
+            ))));
+        }
+        let node = &self.graph[id];
+        // an atom is written again instead, sharing one cannot be seen
+        let label = (self.reached[&id] > 1 && !matches!(node, Node::Atom(_))).then(|| {
+            let label = self.labels.len() as u64;
+            self.labels.insert(id, label);
+            label
+        });
+        let mut all = |elems: &[NodeId]| {
+            elems
+                .iter()
+                .map(|elem| self.notation(*elem))
+                .collect::<Result<_, _>>()
+        };
+        let body = match node {
+            Node::Atom(value) => return value.clone().try_into(),
+            Node::List(elems) => ENotationBody::Container(Container::List(List::PL(PList {
+                elems: all(elems)?,
+                tail: None,
+            }))),
+            Node::DottedList(elems, tail) => {
+                ENotationBody::Container(Container::List(List::PL(PList {
+                    elems: all(elems)?,
+                    tail: Some(Box::new(self.notation(*tail)?)),
+                })))
+            }
+            Node::Vector(elems) => {
+                ENotationBody::Container(Container::Vector(Vector::PV(PVector {
+                    elems: all(elems)?,
+                })))
+            }
+            Node::Set(elems) => {
+                ENotationBody::Container(Container::Set(Set { elems: all(elems)? }))
+            }
+            Node::Map(pairs) => ENotationBody::Container(Container::Object(Object {
+                pairs: pairs
+                    .iter()
+                    .map(|(key, value)| {
+                        Ok(ObjectPair {
+                            key: identifier(key)?,
+                            value: self.notation(*value)?,
+                        })
+                    })
+                    .collect::<Result<_, WriteError>>()?,
+            })),
+            Node::Hash(flavor, entries) => ENotationBody::Container(Container::Hash(HashTable {
+                flavor: *flavor,
+                entries: entries
+                    .iter()
+                    .map(|(key, value)| {
+                        Ok(HashEntry {
+                            key: self.notation(*key)?,
+                            value: self.notation(*value)?,
+                        })
+                    })
+                    .collect::<Result<_, WriteError>>()?,
+            })),
+            Node::Quoted(kind, value) => kind.wrap(Box::new(self.notation(*value)?)),
+        };
+        Ok(match label {
+            Some(label) => notation(ENotationBody::Labeling(Labeling::Label(Label {
+                label,
+                value: Box::new(notation(body)),
+            }))),
+            None => notation(body),
+        })
+    }
+}
+
+fn notation(body: ENotationBody) -> ENotation {
+    ENotation {
+        span: DiagnosticSpan::default(),
+        body,
+    }
+}
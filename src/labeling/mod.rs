@@ -0,0 +1,153 @@
+//! Datum labels, `#n=` names a notation and `#n#` refers to it again.
+//!
+//! A label is visible from where its `#n=` starts to the end of the top-level notation it is
+//! in, so its own value can refer to it and build a cycle. The tree keeps labels as written,
+//! [`Graph`] resolves them to shared nodes.
+use std::{collections::HashMap, fmt::Display};
+
+use from_pest::ConversionError;
+use pest::Span;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    convert::{from_pest, one_of, parsed},
+    visit::{self, Visit},
+    DiagnosticSpan, ENotation, ENotationBody, ParseError, ParseErrorKind, Rule,
+};
+
+mod graph;
+pub use graph::{Graph, Node, NodeId};
+
+#[cfg(test)]
+mod tests;
+
+fn parse_label(input: Span) -> Result<u64, ParseErrorKind> {
+    let text = input.as_str();
+    text[1..text.len() - 1]
+        .parse()
+        .map_err(|_| ParseErrorKind::InvalidLiteral(format!("label `{}` is too large", text)))
+}
+
+/// `#n=value`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Label {
+    pub label: u64,
+    pub value: Box<ENotation>,
+}
+
+/// `#n#`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct LabelRef {
+    pub label: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Labeling {
+    Label(Label),
+    LabelRef(LabelRef),
+}
+
+from_pest! {
+    Label = Rule::label => |_, inner| {
+        let def = inner.next().ok_or(ConversionError::NoMatch)?;
+        Ok(Label {
+            label: parsed(def.as_span(), parse_label)?,
+            value: Box::from_pest(inner)?,
+        })
+    };
+    LabelRef = Rule::label_ref => |span, _| Ok(LabelRef { label: parsed(span, parse_label)? });
+    Labeling = Rule::labeling => |_, inner| one_of!(inner, Labeling::Label, Labeling::LabelRef);
+}
+
+impl Display for Labeling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Labeling::Label(label) => write!(f, "{}", label),
+            Labeling::LabelRef(label_ref) => write!(f, "{}", label_ref),
+        }
+    }
+}
+impl Display for Label {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}={}", self.label, self.value)
+    }
+}
+impl Display for LabelRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}#", self.label)
+    }
+}
+
+/// Collects every label that is defined twice and every reference to a label that is not
+/// defined in `notation`, a top-level notation is a scope of its own.
+pub(crate) fn check(notation: &ENotation, errors: &mut Vec<ParseError>) {
+    let mut scope = Scope {
+        defined: HashMap::new(),
+        pending: vec![],
+        errors,
+    };
+    scope.visit_notation(notation);
+}
+
+struct Scope<'e> {
+    /// The `#n=` of every label defined so far.
+    defined: HashMap<u64, DiagnosticSpan>,
+    /// Labels whose value is not read yet, a reference to one of them would be the value
+    /// itself, as in `#0=#0#`.
+    pending: Vec<u64>,
+    errors: &'e mut Vec<ParseError>,
+}
+
+impl Visit for Scope<'_> {
+    fn visit_notation(&mut self, node: &ENotation) {
+        match &node.body {
+            ENotationBody::Labeling(Labeling::Label(label)) => {
+                let def = label_def(&node.span);
+                match self.defined.get(&label.label) {
+                    Some(first) => self.errors.push(ParseError {
+                        kind: ParseErrorKind::DuplicateLabel(label.label),
+                        span: def,
+                        expected: vec![],
+                        related: Some(first.clone()),
+                    }),
+                    None => {
+                        self.defined.insert(label.label, def);
+                        self.pending.push(label.label);
+                    }
+                }
+                self.visit_notation(&label.value);
+                self.pending.retain(|pending| *pending != label.label);
+            }
+            ENotationBody::Labeling(Labeling::LabelRef(label_ref)) => {
+                let label = label_ref.label;
+                if !self.defined.contains_key(&label) || self.pending.contains(&label) {
+                    self.errors.push(ParseError {
+                        kind: ParseErrorKind::UndefinedLabel(label),
+                        span: node.span.clone(),
+                        expected: vec![],
+                        related: None,
+                    });
+                }
+            }
+            body => {
+                // inside a container or a quoting the labels around it have a value
+                if !matches!(body, ENotationBody::Literal(_) | ENotationBody::Error(_)) {
+                    self.pending.clear();
+                }
+                visit::visit_notation(self, node);
+            }
+        }
+    }
+}
+
+/// The `#n=` that starts the span of a label, it never spans lines.
+fn label_def(span: &DiagnosticSpan) -> DiagnosticSpan {
+    let len = span.span.find('=').map_or(span.span.len(), |eq| eq + 1);
+    DiagnosticSpan {
+        end_line: span.start_line,
+        end_col: span.start_col + len,
+        end_offset: span.start_offset + len,
+        span: span.span[..len].to_string(),
+        ..span.clone()
+    }
+}
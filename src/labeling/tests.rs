@@ -0,0 +1,99 @@
+use insta::assert_snapshot;
+
+use super::{Graph, Node};
+use crate::{cst, parse_file, parse_file_recovering, parse_notation, ParseErrorKind, Value};
+
+#[test]
+fn parse_label() {
+    assert_snapshot!(parse_notation("#0=(a #0#)").unwrap(), @"#0=(a #0#)");
+    assert_snapshot!(parse_notation("(#1=(x) #1# '#2=#(#2#))").unwrap(), @"(#1=(x) #1# '#2=#(#2#))");
+    assert_snapshot!(parse_notation("#0=#1=(a #0# #1#)").unwrap(), @"#0=#1=(a #0# #1#)");
+    // `#0=` only starts a label, a label reference is not an identifier
+    assert_snapshot!(parse_notation("(#0=a #0#)").unwrap().pretty(80), @"(#0=a #0#)");
+}
+
+#[test]
+fn label_errors() {
+    let err = parse_notation("(#0# #0=a)").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UndefinedLabel(0));
+    assert_snapshot!(err, @"1:2: undefined label `#0#`");
+    let err = parse_notation("(#0=a #0=b)").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::DuplicateLabel(0));
+    assert_snapshot!(err, @"1:7: duplicate label `#0=`");
+    assert_snapshot!(err.related.unwrap().span, @"#0=");
+    // a label is not its own value
+    assert_snapshot!(parse_notation("#0=#0#").unwrap_err(), @"1:4: undefined label `#0#`");
+    assert_snapshot!(parse_notation("#0=#1=#0#").unwrap_err(), @"1:7: undefined label `#0#`");
+    // every top-level notation has its own labels
+    assert_snapshot!(parse_file("#0=a #0#").unwrap_err(), @"1:6: undefined label `#0#`");
+    assert!(parse_file("#0=a #0=b").is_ok());
+    let (_, errors) = parse_file_recovering("#0=(a)\n(#0#)\n#1=(#1#)");
+    assert_snapshot!(errors[0], @"2:2: undefined label `#0#`");
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn lossless_label() {
+    let input = "#0= ( a #0# ) ; cyclic\n";
+    let root = cst::parse(input).unwrap();
+    assert_eq!(root.to_string(), input);
+    assert_eq!(cst::lower(&root), parse_file(input).unwrap());
+}
+
+#[test]
+fn resolve_graph() {
+    let mut graph = Graph::new();
+    let root = graph
+        .insert(&parse_notation("(#0=(x) #0# #1=#0#)").unwrap())
+        .unwrap();
+    let elems = graph[root].children();
+    assert_eq!(elems[0], elems[1]);
+    assert_eq!(elems[0], elems[2]);
+    assert_snapshot!(graph.to_notation(root).unwrap(), @"(#0=(x) #0# #0#)");
+
+    let root = graph
+        .insert(&parse_notation("#0=(a #1=#(b #0# #1#))").unwrap())
+        .unwrap();
+    let vector = graph[root].children()[1];
+    assert_eq!(
+        graph[vector].children(),
+        [graph[vector].children()[0], root, vector]
+    );
+    assert_snapshot!(graph.to_notation(root).unwrap(), @"#0=(a #1=#(b #0# #1#))");
+}
+
+#[test]
+fn cyclic_dotted_list() {
+    let mut graph = Graph::new();
+    let root = graph
+        .insert(&parse_notation("#0=(a b . #0#)").unwrap())
+        .unwrap();
+    assert_eq!(graph[root].children()[2], root);
+    assert_snapshot!(graph.to_notation(root).unwrap(), @"#0=(a b . #0#)");
+}
+
+#[test]
+fn write_graph() {
+    let mut graph = Graph::new();
+    let a = graph.push(Node::Atom(Value::symbol("a")));
+    let list = graph.push(Node::List(vec![a, a]));
+    let quoted = graph.push(Node::Quoted(crate::value::QuoteKind::Quote, list));
+    graph[list] = Node::List(vec![a, quoted, list]);
+    let root = graph.push(Node::Map(vec![
+        ("k".to_string(), list),
+        ("v".to_string(), list),
+    ]));
+    // atoms are written twice, containers reached twice are labeled
+    assert_snapshot!(graph.to_notation(root).unwrap(), @"{k : #0=(a '#0# #0#), v : #0#}");
+    assert_snapshot!(graph.to_notation(list).unwrap(), @"#0=(a '#0# #0#)");
+    assert_snapshot!(graph.to_notation(a).unwrap(), @"a");
+}
+
+#[test]
+fn labels_to_value() {
+    let value: Value = "(#0=(x) #0#)".parse().unwrap();
+    assert_eq!(value, "((x) (x))".parse().unwrap());
+    let err = "#0=(a #0#)".parse::<Value>().unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::CyclicValue);
+    assert_snapshot!(err, @"1:7: a value cannot be cyclic");
+}
@@ -1,17 +1,50 @@
+// `ParseError` carries a whole `DiagnosticSpan`, which is fine for a parser.
+#![allow(clippy::result_large_err)]
+
+mod compare;
 pub mod container;
+mod convert;
+pub mod cst;
+pub mod de;
+pub mod error;
+pub mod fold;
+pub mod labeling;
 pub mod literal;
+pub mod pretty;
 pub mod quoting;
+mod recovery;
+pub mod ser;
 pub mod syntaxing;
+pub mod value;
+pub mod visit;
+pub mod visit_mut;
 
 use std::fmt::Display;
 
-use container::Container;
+use container::{
+    bytevector::Bytevector,
+    hash::{self, HashEntry, HashTable},
+    list::{BList, List, PList},
+    object::{Object, ObjectPair},
+    set::Set,
+    vector::{BVector, PVector, Vector},
+    Container,
+};
+use convert::{from_pest, one_of};
+pub use de::{from_str, Spanned};
+pub use error::{ParseError, ParseErrorKind};
+use from_pest::ConversionError;
+use labeling::{Label, LabelRef, Labeling};
 use literal::Literal;
-use pest_ast::FromPest;
+use pest::{iterators::Pairs, Parser};
 use pest_derive::Parser;
-use quoting::Quoting;
-use serde::{Deserialize, Serialize};
-use syntaxing::Syntaxing;
+use quoting::{QuasiQuote, Quote, Quoting, Unquote, UnquoteSplicing};
+pub use recovery::parse_file_recovering;
+pub use ser::{to_string, to_string_pretty};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+use syntaxing::{QuasiSyntax, Syntax, Syntaxing, Unsyntax, UnsyntaxSplicing};
+pub use value::Value;
+use visit_mut::VisitMut;
 
 #[cfg(test)]
 mod tests;
@@ -22,63 +55,183 @@ pub trait SetDebugFileName {
 }
 
 
+/// Sets the file of every span.
+struct DebugFileName<'a>(&'a str);
+
+impl VisitMut for DebugFileName<'_> {
+    fn visit_span_mut(&mut self, node: &mut DiagnosticSpan) {
+        node.file = Some(self.0.to_string());
+    }
+}
+
+macro_rules! set_debug_file_name {
+    ($($ty:ty => $visit:ident),* $(,)?) => {$(
+        impl SetDebugFileName for $ty {
+            fn set_debug_file_name(&mut self, file_name: &str) {
+                DebugFileName(file_name).$visit(self)
+            }
+        }
+    )*};
+}
+
+set_debug_file_name!(
+    EFile => visit_file_mut,
+    ENotation => visit_notation_mut,
+    ENotationBody => visit_body_mut,
+    Container => visit_container_mut,
+    List => visit_list_mut,
+    PList => visit_plist_mut,
+    BList => visit_blist_mut,
+    Vector => visit_vector_mut,
+    PVector => visit_pvector_mut,
+    BVector => visit_bvector_mut,
+    Set => visit_set_mut,
+    Object => visit_object_mut,
+    ObjectPair => visit_object_pair_mut,
+    Bytevector => visit_bytevector_mut,
+    HashTable => visit_hash_table_mut,
+    HashEntry => visit_hash_entry_mut,
+    Quoting => visit_quoting_mut,
+    Quote => visit_quote_mut,
+    QuasiQuote => visit_quasi_quote_mut,
+    Unquote => visit_unquote_mut,
+    UnquoteSplicing => visit_unquote_splicing_mut,
+    Syntaxing => visit_syntaxing_mut,
+    Syntax => visit_syntax_mut,
+    QuasiSyntax => visit_quasi_syntax_mut,
+    Unsyntax => visit_unsyntax_mut,
+    UnsyntaxSplicing => visit_unsyntax_splicing_mut,
+    Labeling => visit_labeling_mut,
+    Label => visit_label_mut,
+    LabelRef => visit_label_ref_mut,
+);
+
 #[derive(Parser)]
 #[grammar = "notation.pest"]
 pub struct ENotationParser;
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::notation_))]
+/// Parses a whole file of notations.
+pub fn parse_file(input: &str) -> Result<EFile, ParseError> {
+    convert::convert(&mut parse_pairs(Rule::file, input)?)
+}
+
+/// Parses exactly one notation, surrounding whitespace and comments are allowed.
+pub fn parse_notation(input: &str) -> Result<ENotation, ParseError> {
+    let notation = convert::convert(&mut parse_pairs(Rule::single_notation, input)?)?;
+    check(&notation)?;
+    Ok(notation)
+}
+
+fn parse_pairs(rule: Rule, input: &str) -> Result<Pairs<'_, Rule>, ParseError> {
+    ENotationParser::parse(rule, input).map_err(|err| ParseError::from_pest(input, err))
+}
+
+/// Labels are resolved and hash keys compared on the converted notation, each top-level
+/// notation is checked on its own.
+pub(crate) fn check(notation: &ENotation) -> Result<(), ParseError> {
+    let mut errors = vec![];
+    labeling::check(notation, &mut errors);
+    hash::check_keys(notation, &mut errors);
+    match errors.into_iter().min_by_key(|err| err.span.start_offset) {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ENotationBody {
     Literal(Literal),
     Container(Container),
     Quoting(Quoting),
     Syntaxing(Syntaxing),
+    Labeling(Labeling),
+    Error(ErrorNode),
 }
 
-impl SetDebugFileName for ENotationBody {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        match self {
-            ENotationBody::Container(c) => c.set_debug_file_name(file_name),
-            ENotationBody::Quoting(q) => q.set_debug_file_name(file_name),
-            ENotationBody::Syntaxing(s) => s.set_debug_file_name(file_name),
-            _ => {}
-        }
-    }
-}
+/// A region the recovering parser skipped, its source is kept in the span of the notation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ErrorNode;
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::notation))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ENotation {
-    #[pest_ast(outer(with(DiagnosticSpan::from_pest_span)))]
     pub span: DiagnosticSpan,
     pub body: ENotationBody,
 }
 
-impl SetDebugFileName for ENotation {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        self.span.file = Some(file_name.to_string());
-        self.body.set_debug_file_name(file_name);
-    }
-}
-
-#[derive(Debug, FromPest)]
-#[pest_ast(rule(Rule::file))]
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct EFile {
     pub notations: Vec<ENotation>,
+    #[serde(skip)]
     _eoi: Eoi,
 }
 
-impl SetDebugFileName for EFile {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        for e in self.notations.iter_mut() {
-            e.set_debug_file_name(file_name);
+#[derive(Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Eoi {}
+
+// the grammar never produces an error node, only `parse_file_recovering` builds them
+from_pest! {
+    ENotationBody = Rule::notation_ => |_, inner| one_of!(
+        inner,
+        ENotationBody::Literal,
+        ENotationBody::Container,
+        ENotationBody::Quoting,
+        ENotationBody::Syntaxing,
+        ENotationBody::Labeling,
+    );
+    ENotation = Rule::notation => |span, inner| {
+        Ok(ENotation {
+            span: DiagnosticSpan::from_pest_span(span),
+            body: ENotationBody::from_pest(inner)?,
+        })
+    };
+    EFile = Rule::file => |_, inner| {
+        let mut notations = vec![];
+        while inner.peek().is_some_and(|pair| pair.as_rule() == Rule::notation) {
+            let notation = ENotation::from_pest(inner)?;
+            check(&notation).map_err(ConversionError::Malformed)?;
+            notations.push(notation);
+        }
+        Ok(EFile {
+            notations,
+            _eoi: Eoi::from_pest(inner)?,
+        })
+    };
+    Eoi = Rule::EOI => |_, _| Ok(Eoi {});
+}
+
+/// The version of the serde representation of the AST, bumped whenever a change to the AST
+/// changes what it serializes to.
+pub const AST_VERSION: u32 = 6;
+
+/// A tree with the [`AST_VERSION`] it was serialized with, so a stored tree of another
+/// version fails to deserialize instead of being misread.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Versioned<T> {
+    #[serde(deserialize_with = "current_version")]
+    pub version: u32,
+    pub tree: T,
+}
+
+impl<T> Versioned<T> {
+    pub fn new(tree: T) -> Self {
+        Self {
+            version: AST_VERSION,
+            tree,
         }
     }
 }
 
-#[derive(Debug, FromPest)]
-#[pest_ast(rule(Rule::EOI))]
-struct Eoi {}
+fn current_version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    let version = u32::deserialize(deserializer)?;
+    if version == AST_VERSION {
+        Ok(version)
+    } else {
+        Err(D::Error::custom(format!(
+            "AST version {} is not supported, expected {}",
+            version, AST_VERSION
+        )))
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[derive(Default)]
@@ -132,6 +285,8 @@ impl Display for ENotation {
             Container(c) => write!(f, "{}", c),
             Quoting(q) => write!(f, "{}", q),
             Syntaxing(s) => write!(f, "{}", s),
+            Labeling(l) => write!(f, "{}", l),
+            Error(_) => write!(f, "{}", self.span.span),
         }
     }
 }
@@ -1,144 +1,651 @@
 use std::fmt::Display;
 
+use num::{BigInt, BigRational, Num, ToPrimitive, Zero};
 use pest::Span;
-use pest_ast::FromPest;
+use serde::{Deserialize, Serialize};
 
-use crate::Rule;
+use crate::{
+    convert::{from_pest, one_of, parsed},
+    ParseErrorKind, Rule,
+};
 
 #[cfg(test)]
 mod test;
 
-fn parse_bool(input: Span) -> Result<bool, ()> {
+fn parse_bool(input: Span) -> Result<bool, ParseErrorKind> {
     match input.as_str() {
         "#t" => Ok(true),
         "#f" => Ok(false),
         _ => Ok(true),
     }
 }
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::boolean))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Boolean {
-    #[pest_ast(outer(with(parse_bool), with(Result::unwrap)))]
     pub value: bool,
 }
 
-fn parse_char(input: Span) -> Result<char, ()> {
-    println!("hello {:?}", input);
-    match input.as_str() {
-        "#\\newline" => Ok('\n'),
-        "#\\return" => Ok('\r'),
-        "#\\space" => Ok(' '),
-        "#\\tab" => Ok('\t'),
-        i => {
-            let mut chars = i.chars();
-            if chars.next() == Some('#') && chars.next() == Some('\\') {
-                if let Some(c) = chars.next() {
-                    return Ok(c);
-                }
-            }
-            Err(())
-        }
+/// Character names from R7RS and Racket, `Display` uses the first name of a character.
+const CHAR_NAMES: [(&str, char); 14] = [
+    ("null", '\0'),
+    ("nul", '\0'),
+    ("alarm", '\u{7}'),
+    ("backspace", '\u{8}'),
+    ("tab", '\t'),
+    ("newline", '\n'),
+    ("linefeed", '\n'),
+    ("vtab", '\u{b}'),
+    ("page", '\u{c}'),
+    ("return", '\r'),
+    ("escape", '\u{1b}'),
+    ("space", ' '),
+    ("delete", '\u{7f}'),
+    ("rubout", '\u{7f}'),
+];
+
+fn parse_char(input: Span) -> Result<char, ParseErrorKind> {
+    let body = input.as_str().strip_prefix("#\\").ok_or_else(|| {
+        ParseErrorKind::InvalidLiteral(format!("`{}` is not a character", input.as_str()))
+    })?;
+    let mut chars = body.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(c);
+    }
+    if let Some(hex) = body.strip_prefix("u{").and_then(|b| b.strip_suffix('}')) {
+        return code_point(hex);
+    }
+    match body.strip_prefix('x') {
+        Some(hex) if hex.chars().all(|c| c.is_ascii_hexdigit()) => code_point(hex),
+        _ => CHAR_NAMES
+            .iter()
+            .find(|(name, _)| *name == body)
+            .map(|(_, c)| *c)
+            .ok_or_else(|| ParseErrorKind::UnknownCharName(body.to_string())),
     }
 }
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::char))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Char {
-    #[pest_ast(outer(with(parse_char), with(Result::unwrap)))]
     pub value: char,
 }
 
-fn parse_int(input: Span) -> Result<i64, ()> {
-    let mut chars = input.as_str().chars();
-    let sign = match chars.next() {
-        Some('+') => 1,
-        Some('-') => -1,
-        Some(_) => {
-            chars = input.as_str().chars();
-            1
+/// An integer value, `Big` is only used when the value does not fit into `i64`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum IntegerValue {
+    Small(i64),
+    Big(BigInt),
+}
+
+/// The base a numeric literal is written in, set by a `#b`, `#o`, `#d` or `#x` prefix.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+pub enum Radix {
+    Binary,
+    Octal,
+    #[default]
+    Decimal,
+    Hexadecimal,
+}
+
+impl Radix {
+    pub fn base(self) -> u32 {
+        match self {
+            Radix::Binary => 2,
+            Radix::Octal => 8,
+            Radix::Decimal => 10,
+            Radix::Hexadecimal => 16,
         }
-        None => return Err(()),
-    };
-    let mut value = 0;
-    for c in chars {
-        if c == '_' {
-            continue;
+    }
+
+    /// The prefix printed for this radix, decimal needs none.
+    fn prefix(self) -> &'static str {
+        match self {
+            Radix::Binary => "#b",
+            Radix::Octal => "#o",
+            Radix::Decimal => "",
+            Radix::Hexadecimal => "#x",
+        }
+    }
+}
+
+/// An explicit `#e` or `#i` prefix of a numeric literal.
+///
+/// The literal kinds keep the prefix as written without applying it, so `#e1.5` is still a
+/// [`Float`] and `#i1/3` a [`Rational`], the parser only rejects an `#e` float that has no
+/// exact value. [`Value`](crate::Value) applies it, turning them into `3/2` and `0.333…`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Exactness {
+    Exact,
+    Inexact,
+}
+
+/// Splits the radix and exactness prefixes off a numeric literal, in whatever order they
+/// were written.
+fn split_prefix(input: &str) -> (Radix, Option<Exactness>, &str) {
+    let mut radix = Radix::Decimal;
+    let mut exactness = None;
+    let mut rest = input;
+    while let Some(mark) = rest.strip_prefix('#').and_then(|r| r.chars().next()) {
+        match mark.to_ascii_lowercase() {
+            'b' => radix = Radix::Binary,
+            'o' => radix = Radix::Octal,
+            'd' => radix = Radix::Decimal,
+            'x' => radix = Radix::Hexadecimal,
+            'e' => exactness = Some(Exactness::Exact),
+            'i' => exactness = Some(Exactness::Inexact),
+            _ => break,
         }
-        value = value * 10 + c.to_digit(10).ok_or(())? as i64;
+        rest = &rest[2..];
     }
-    Ok(sign * value)
+    (radix, exactness, rest)
 }
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::int))]
+fn parse_radix(input: Span) -> Radix {
+    split_prefix(input.as_str()).0
+}
+
+fn parse_exactness(input: Span) -> Option<Exactness> {
+    split_prefix(input.as_str()).1
+}
+
+fn parse_bigint(input: &str, radix: Radix) -> Result<BigInt, ParseErrorKind> {
+    let digits = input.replace('_', "");
+    BigInt::from_str_radix(&digits, radix.base())
+        .map_err(|_| ParseErrorKind::InvalidLiteral(format!("`{}` is not an integer", input)))
+}
+
+fn parse_integer(input: &str, radix: Radix) -> Result<IntegerValue, ParseErrorKind> {
+    let value = parse_bigint(input, radix)?;
+    Ok(match value.to_i64() {
+        Some(value) => IntegerValue::Small(value),
+        None => IntegerValue::Big(value),
+    })
+}
+
+fn parse_int(input: Span) -> Result<IntegerValue, ParseErrorKind> {
+    let (radix, _, digits) = split_prefix(input.as_str());
+    parse_integer(digits, radix)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Integer {
-    #[pest_ast(outer(with(parse_int), with(Result::unwrap)))]
-    pub value: i64,
+    pub value: IntegerValue,
+    pub radix: Radix,
+    pub exactness: Option<Exactness>,
+}
+
+impl Integer {
+    pub fn as_i64(&self) -> Option<i64> {
+        match &self.value {
+            IntegerValue::Small(value) => Some(*value),
+            IntegerValue::Big(_) => None,
+        }
+    }
+
+    pub fn as_bigint(&self) -> BigInt {
+        match &self.value {
+            IntegerValue::Small(value) => BigInt::from(*value),
+            IntegerValue::Big(value) => value.clone(),
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        match &self.value {
+            IntegerValue::Small(value) => *value as f64,
+            IntegerValue::Big(value) => value.to_f64().unwrap_or(f64::NAN),
+        }
+    }
+}
+
+fn parse_ratio(input: &str, radix: Radix) -> Result<BigRational, ParseErrorKind> {
+    let (p, q) = input
+        .split_once('/')
+        .ok_or_else(|| ParseErrorKind::InvalidLiteral(format!("`{}` is not a rational", input)))?;
+    let (p, q) = (parse_bigint(p, radix)?, parse_bigint(q, radix)?);
+    if q.is_zero() {
+        return Err(ParseErrorKind::ZeroDenominator);
+    }
+    Ok(BigRational::new(p, q))
 }
 
-fn parse_rational(input: Span) -> Result<(i64, i64), ()> {
-    let v = input.as_str().split('/').collect::<Vec<_>>();
-    let p: i64 = v[0].parse().map_err(|_| ())?;
-    let q: i64 = v[1].parse().map_err(|_| ())?;
-    Ok((p, q))
+fn parse_rational(input: Span) -> Result<BigRational, ParseErrorKind> {
+    let (radix, _, digits) = split_prefix(input.as_str());
+    parse_ratio(digits, radix)
 }
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::rational))]
+
+/// A rational in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Rational {
-    #[pest_ast(outer(with(parse_rational), with(Result::unwrap)))]
-    pub value: (i64, i64),
+    pub value: BigRational,
+    pub radix: Radix,
+    pub exactness: Option<Exactness>,
+}
+
+impl Rational {
+    /// The value if it is an integer that fits into `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_bigint().and_then(|value| value.to_i64())
+    }
+
+    /// The value if it is an integer.
+    pub fn as_bigint(&self) -> Option<BigInt> {
+        if self.value.is_integer() {
+            Some(self.value.to_integer())
+        } else {
+            None
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.value.to_f64().unwrap_or(f64::NAN)
+    }
 }
 
-fn span_into_str(span: Span) -> &str {
-    span.as_str()
+fn parse_f64(input: &str) -> Result<f64, ParseErrorKind> {
+    match input.to_ascii_lowercase().as_str() {
+        "+inf.0" => Ok(f64::INFINITY),
+        "-inf.0" => Ok(f64::NEG_INFINITY),
+        "+nan.0" => Ok(f64::NAN),
+        "-nan.0" => Ok(-f64::NAN),
+        digits => digits
+            .replace('_', "")
+            .parse()
+            .map_err(|_| ParseErrorKind::InvalidLiteral(format!("`{}` is not a float", input))),
+    }
 }
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::float))]
+fn parse_float(input: Span) -> Result<f64, ParseErrorKind> {
+    let (_, exactness, digits) = split_prefix(input.as_str());
+    let value = parse_f64(digits)?;
+    if exactness == Some(Exactness::Exact) && !value.is_finite() {
+        return Err(ParseErrorKind::InvalidLiteral(format!(
+            "`{}` has no exact value",
+            input.as_str()
+        )));
+    }
+    Ok(value)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Float {
-    #[pest_ast(outer(with(span_into_str), with(str::parse), with(Result::unwrap)))]
     pub value: f64,
+    /// Floats are always decimal, so only the exactness prefix is kept.
+    pub exactness: Option<Exactness>,
+}
+
+impl Float {
+    /// The value if it is integral and fits into `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        if self.value.fract() == 0.0
+            && self.value >= i64::MIN as f64
+            && self.value < i64::MAX as f64
+        {
+            Some(self.value as i64)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.value
+    }
+}
+
+/// A real part of a complex literal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Real {
+    Int(Integer),
+    Rational(Rational),
+    Float(Float),
+}
+
+impl Real {
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Real::Int(i) => i.to_f64(),
+            Real::Rational(r) => r.to_f64(),
+            Real::Float(fl) => fl.to_f64(),
+        }
+    }
 }
 
-fn remove_quotes(s: &str) -> String {
-    s.trim_matches(|c| c == '\"' || c == '\'').to_string()
+fn parse_real(input: &str) -> Result<Real, ParseErrorKind> {
+    let lower = input.to_ascii_lowercase();
+    if input.contains('/') {
+        Ok(Real::Rational(Rational {
+            value: parse_ratio(input, Radix::Decimal)?,
+            radix: Radix::Decimal,
+            exactness: None,
+        }))
+    } else if lower.contains(['.', 'e']) {
+        Ok(Real::Float(Float {
+            value: parse_f64(input)?,
+            exactness: None,
+        }))
+    } else {
+        Ok(Real::Int(Integer {
+            value: parse_integer(input, Radix::Decimal)?,
+            radix: Radix::Decimal,
+            exactness: None,
+        }))
+    }
 }
-fn parse_string(input: Span) -> Result<String, ()> {
-    Ok(remove_quotes(input.as_str()))
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ComplexValue {
+    /// `re+imi`, a missing real part is an exact `0`.
+    Rectangular(num::Complex<Real>),
+    /// `magnitude@angle`
+    Polar { magnitude: Real, angle: Real },
 }
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::string))]
+fn parse_complex(input: Span) -> Result<ComplexValue, ParseErrorKind> {
+    let digits = split_prefix(input.as_str()).2;
+    if let Some((magnitude, angle)) = digits.split_once('@') {
+        return Ok(ComplexValue::Polar {
+            magnitude: parse_real(magnitude)?,
+            angle: parse_real(angle)?,
+        });
+    }
+    let digits = &digits[..digits.len() - 1];
+    // the imaginary part starts at the last sign that is not part of an exponent
+    let bytes = digits.as_bytes();
+    let split = (1..bytes.len())
+        .rev()
+        .find(|&i| matches!(bytes[i], b'+' | b'-') && !matches!(bytes[i - 1], b'e' | b'E'))
+        .unwrap_or(0);
+    let re = if split == 0 {
+        Real::Int(Integer {
+            value: IntegerValue::Small(0),
+            radix: Radix::Decimal,
+            exactness: None,
+        })
+    } else {
+        parse_real(&digits[..split])?
+    };
+    let im = match &digits[split..] {
+        // `+i` and `-i`
+        sign @ ("+" | "-") => parse_real(&format!("{}1", sign))?,
+        im => parse_real(im)?,
+    };
+    Ok(ComplexValue::Rectangular(num::Complex::new(re, im)))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Complex {
+    pub value: ComplexValue,
+    pub exactness: Option<Exactness>,
+}
+
+impl Complex {
+    /// The value as a rectangular complex of floats, possibly rounded.
+    pub fn to_complex64(&self) -> num::Complex<f64> {
+        match &self.value {
+            ComplexValue::Rectangular(c) => num::Complex::new(c.re.to_f64(), c.im.to_f64()),
+            ComplexValue::Polar { magnitude, angle } => {
+                num::Complex::from_polar(magnitude.to_f64(), angle.to_f64())
+            }
+        }
+    }
+}
+
+fn code_point(hex: &str) -> Result<char, ParseErrorKind> {
+    u32::from_str_radix(hex, 16)
+        .ok()
+        .and_then(char::from_u32)
+        .ok_or_else(|| {
+            ParseErrorKind::InvalidLiteral(format!("`{}` is not a Unicode scalar value", hex))
+        })
+}
+
+fn parse_string(input: Span) -> Result<String, ParseErrorKind> {
+    let source = input.as_str();
+    unescape(&source[1..source.len() - 1])
+}
+
+/// The content of a string or byte string between its quotes, with the escapes replaced.
+fn unescape(body: &str) -> Result<String, ParseErrorKind> {
+    let mut value = String::with_capacity(body.len());
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('a') => value.push('\u{7}'),
+            Some('b') => value.push('\u{8}'),
+            Some('t') => value.push('\t'),
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some(c @ ('"' | '\\' | '|')) => value.push(c),
+            Some('x') => {
+                let hex: String = chars.by_ref().take_while(|c| *c != ';').collect();
+                value.push(code_point(&hex)?);
+            }
+            Some('u') => {
+                chars.next_if_eq(&'{');
+                let hex: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                value.push(code_point(&hex)?);
+            }
+            // line continuation, drops the newline and the indentation around it
+            Some(mut c @ (' ' | '\t' | '\n' | '\r')) => {
+                while c == ' ' || c == '\t' {
+                    // the grammar guarantees a newline follows
+                    c = chars.next().unwrap_or('\n');
+                }
+                if c == '\r' {
+                    chars.next_if_eq(&'\n');
+                }
+                while chars.next_if(|c| *c == ' ' || *c == '\t').is_some() {}
+            }
+            c => {
+                return Err(ParseErrorKind::InvalidLiteral(format!(
+                    "unknown escape `\\{}`",
+                    c.map(String::from).unwrap_or_default()
+                )))
+            }
+        }
+    }
+    Ok(value)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct String_ {
-    #[pest_ast(outer(with(parse_string), with(Result::unwrap)))]
     pub value: String,
 }
 
-fn parse_identifier(input: Span) -> Result<String, ()> {
+fn parse_byte_string(input: Span) -> Result<Vec<u8>, ParseErrorKind> {
+    let source = input.as_str();
+    let body = &source[2..source.len() - 1];
+    if let Some(c) = body.chars().find(|c| !c.is_ascii()) {
+        return Err(ParseErrorKind::InvalidLiteral(format!(
+            "`{}` is not ASCII, write its bytes as `\\x..;` escapes",
+            c
+        )));
+    }
+    unescape(body)?
+        .chars()
+        .map(|c| {
+            u8::try_from(c).map_err(|_| {
+                ParseErrorKind::InvalidLiteral(format!("`\\x{:x};` is not a byte", c as u32))
+            })
+        })
+        .collect()
+}
+
+/// `#"..."`, a string of bytes with the escapes of a string, `\x..;` escapes must be bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ByteString {
+    pub value: Vec<u8>,
+}
+
+/// The value of an `int` that is an exact integer from 0 to 255.
+pub(crate) fn parse_byte(input: Span) -> Option<u8> {
+    if parse_exactness(input) == Some(Exactness::Inexact) {
+        return None;
+    }
+    match parse_int(input).ok()? {
+        IntegerValue::Small(value) => u8::try_from(value).ok(),
+        IntegerValue::Big(_) => None,
+    }
+}
+
+fn parse_identifier(input: Span) -> Result<String, ParseErrorKind> {
     let s = input.as_str();
     if s.parse::<i64>().is_ok() {
-        return Err(());
+        return Err(ParseErrorKind::InvalidLiteral(format!(
+            "`{}` is a number, not an identifier",
+            s
+        )));
     }
     Ok(s.to_string())
 }
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::identifier))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Identifier {
-    #[pest_ast(outer(with(parse_identifier), with(Result::unwrap)))]
     pub name: String,
 }
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::literal))]
+impl Identifier {
+    /// The identifier of `symbol`, quoted as `|symbol|` unless it reads back as a plain
+    /// identifier. `None` if it needs quoting but contains a `|`.
+    pub fn from_symbol(symbol: &str) -> Option<Identifier> {
+        match crate::parse_notation(symbol).map(|n| n.body) {
+            Ok(crate::ENotationBody::Literal(Literal::Identifier(i))) if i.name == symbol => {
+                Some(i)
+            }
+            _ if symbol.contains('|') => None,
+            _ => Some(Identifier {
+                name: format!("|{}|", symbol),
+            }),
+        }
+    }
+
+    /// The name without the bars of a quoted identifier, `|a b|` is the symbol `a b`.
+    pub fn symbol(&self) -> &str {
+        self.name
+            .strip_prefix('|')
+            .and_then(|name| name.strip_suffix('|'))
+            .unwrap_or(&self.name)
+    }
+}
+
+fn parse_keyword(input: Span) -> Result<String, ParseErrorKind> {
+    match &input.as_str()["#:".len()..] {
+        "" => Err(ParseErrorKind::EmptyKeyword),
+        name => Ok(name.to_string()),
+    }
+}
+
+/// `#:name`, names the argument after it in a form, see
+/// [`List::arguments`](crate::container::list::List::arguments).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Keyword {
+    /// The name without the `#:`.
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Literal {
     Boolean(Boolean),
     Char(Char),
     Float(Float),
     Rational(Rational),
     Int(Integer),
+    Complex(Complex),
     String_(String_),
     Identifier(Identifier),
+    Keyword(Keyword),
+    ByteString(ByteString),
+}
+
+from_pest! {
+    Boolean = Rule::boolean => |span, _| Ok(Boolean { value: parsed(span, parse_bool)? });
+    Char = Rule::char => |span, _| Ok(Char { value: parsed(span, parse_char)? });
+    Integer = Rule::int => |span, _| {
+        Ok(Integer {
+            value: parsed(span, parse_int)?,
+            radix: parse_radix(span),
+            exactness: parse_exactness(span),
+        })
+    };
+    Rational = Rule::rational => |span, _| {
+        Ok(Rational {
+            value: parsed(span, parse_rational)?,
+            radix: parse_radix(span),
+            exactness: parse_exactness(span),
+        })
+    };
+    Float = Rule::float => |span, _| {
+        Ok(Float {
+            value: parsed(span, parse_float)?,
+            exactness: parse_exactness(span),
+        })
+    };
+    Complex = Rule::complex => |span, _| {
+        Ok(Complex {
+            value: parsed(span, parse_complex)?,
+            exactness: parse_exactness(span),
+        })
+    };
+    String_ = Rule::string => |span, _| Ok(String_ { value: parsed(span, parse_string)? });
+    ByteString = Rule::byte_string => |span, _| {
+        Ok(ByteString {
+            value: parsed(span, parse_byte_string)?,
+        })
+    };
+    Identifier = Rule::identifier => |span, _| {
+        Ok(Identifier {
+            name: parsed(span, parse_identifier)?,
+        })
+    };
+    Keyword = Rule::keyword => |span, _| Ok(Keyword { name: parsed(span, parse_keyword)? });
+    Literal = Rule::literal => |_, inner| {
+        one_of!(
+            inner,
+            Literal::Boolean,
+            Literal::Char,
+            Literal::Float,
+            Literal::Rational,
+            Literal::Int,
+            Literal::Complex,
+            Literal::String_,
+            Literal::Identifier,
+            Literal::Keyword,
+            Literal::ByteString,
+        )
+    };
+}
+
+impl Literal {
+    /// The value of an integral numeric literal that fits into `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Literal::Int(i) => i.as_i64(),
+            Literal::Rational(r) => r.as_i64(),
+            _ => None,
+        }
+    }
+
+    /// The value of an integral exact numeric literal.
+    pub fn as_bigint(&self) -> Option<BigInt> {
+        match self {
+            Literal::Int(i) => Some(i.as_bigint()),
+            Literal::Rational(r) => r.as_bigint(),
+            _ => None,
+        }
+    }
+
+    /// The value of any numeric literal, possibly rounded.
+    pub fn to_f64(&self) -> Option<f64> {
+        match self {
+            Literal::Int(i) => Some(i.to_f64()),
+            Literal::Rational(r) => Some(r.to_f64()),
+            Literal::Float(fl) => Some(fl.to_f64()),
+            _ => None,
+        }
+    }
 }
 
 impl Display for Boolean {
@@ -152,33 +659,112 @@ impl Display for Boolean {
 }
 impl Display for Char {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.value {
-            ' ' => write!(f, "#\\space"),
-            '\t' => write!(f, "#\\tab"),
-            '\r' => write!(f, "#\\return"),
-            '\n' => write!(f, "#\\newline"),
-            c => write!(f, "#\\{}", c),
+        match CHAR_NAMES.iter().find(|(_, c)| *c == self.value) {
+            Some((name, _)) => write!(f, "#\\{}", name),
+            // invisible characters would not read back
+            None if self.value.is_control() || self.value.is_whitespace() => {
+                write!(f, "#\\x{:x}", self.value as u32)
+            }
+            None => write!(f, "#\\{}", self.value),
         }
     }
 }
+impl Display for Exactness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Exactness::Exact => write!(f, "#e"),
+            Exactness::Inexact => write!(f, "#i"),
+        }
+    }
+}
+fn write_prefix(
+    f: &mut std::fmt::Formatter<'_>,
+    radix: Radix,
+    exactness: Option<Exactness>,
+) -> std::fmt::Result {
+    if let Some(exactness) = exactness {
+        write!(f, "{}", exactness)?;
+    }
+    write!(f, "{}", radix.prefix())
+}
 impl Display for Integer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.value)
+        write_prefix(f, self.radix, self.exactness)?;
+        match (&self.value, self.radix) {
+            (IntegerValue::Small(value), Radix::Decimal) => write!(f, "{}", value),
+            _ => write!(f, "{}", self.as_bigint().to_str_radix(self.radix.base())),
+        }
     }
 }
 impl Display for Float {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.value)
+        write_prefix(f, Radix::Decimal, self.exactness)?;
+        let sign = if self.value.is_sign_negative() {
+            "-"
+        } else {
+            "+"
+        };
+        if self.value.is_nan() {
+            write!(f, "{}nan.0", sign)
+        } else if self.value.is_infinite() {
+            write!(f, "{}inf.0", sign)
+        } else {
+            // `Debug` is the shortest form that reads back to the same value, and keeps the `.0`
+            write!(f, "{:?}", self.value)
+        }
     }
 }
 impl Display for Rational {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}/{}", self.value.0, self.value.1)
+        write_prefix(f, self.radix, self.exactness)?;
+        let base = self.radix.base();
+        write!(
+            f,
+            "{}/{}",
+            self.value.numer().to_str_radix(base),
+            self.value.denom().to_str_radix(base)
+        )
+    }
+}
+impl Display for Real {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Real::Int(i) => write!(f, "{}", i),
+            Real::Rational(r) => write!(f, "{}", r),
+            Real::Float(fl) => write!(f, "{}", fl),
+        }
+    }
+}
+impl Display for Complex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_prefix(f, Radix::Decimal, self.exactness)?;
+        match &self.value {
+            ComplexValue::Rectangular(c) => {
+                let im = c.im.to_string();
+                let sign = if im.starts_with(['+', '-']) { "" } else { "+" };
+                write!(f, "{}{}{}i", c.re, sign, im)
+            }
+            ComplexValue::Polar { magnitude, angle } => write!(f, "{}@{}", magnitude, angle),
+        }
     }
 }
 impl Display for String_ {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "\"{}\"", self.value)
+        write!(f, "\"")?;
+        for c in self.value.chars() {
+            match c {
+                '"' => write!(f, "\\\"")?,
+                '\\' => write!(f, "\\\\")?,
+                '\n' => write!(f, "\\n")?,
+                '\t' => write!(f, "\\t")?,
+                '\r' => write!(f, "\\r")?,
+                '\u{7}' => write!(f, "\\a")?,
+                '\u{8}' => write!(f, "\\b")?,
+                c if c.is_control() => write!(f, "\\x{:x};", c as u32)?,
+                c => write!(f, "{}", c)?,
+            }
+        }
+        write!(f, "\"")
     }
 }
 impl Display for Identifier {
@@ -186,6 +772,30 @@ impl Display for Identifier {
         write!(f, "{}", self.name)
     }
 }
+impl Display for ByteString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#\"")?;
+        for b in &self.value {
+            match b {
+                b'"' => write!(f, "\\\"")?,
+                b'\\' => write!(f, "\\\\")?,
+                b'\n' => write!(f, "\\n")?,
+                b'\t' => write!(f, "\\t")?,
+                b'\r' => write!(f, "\\r")?,
+                0x7 => write!(f, "\\a")?,
+                0x8 => write!(f, "\\b")?,
+                b' '..=b'~' => write!(f, "{}", *b as char)?,
+                b => write!(f, "\\x{:x};", b)?,
+            }
+        }
+        write!(f, "\"")
+    }
+}
+impl Display for Keyword {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#:{}", self.name)
+    }
+}
 impl Display for Literal {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -194,8 +804,11 @@ impl Display for Literal {
             Literal::Float(fl) => write!(f, "{}", fl),
             Literal::Rational(r) => write!(f, "{}", r),
             Literal::Int(i) => write!(f, "{}", i),
+            Literal::Complex(c) => write!(f, "{}", c),
             Literal::String_(s) => write!(f, "{}", s),
             Literal::Identifier(i) => write!(f, "{}", i),
+            Literal::Keyword(k) => write!(f, "{}", k),
+            Literal::ByteString(b) => write!(f, "{}", b),
         }
     }
 }
@@ -111,6 +111,43 @@ fn invalid_identifier_that_an_integer() {
     Identifier::from_pest(&mut output).unwrap();
 }
 
+#[test]
+fn parse_keyword() {
+    assert_snapshot!(literal("#:key"), @"#:key");
+    assert_snapshot!(literal("#:1st"), @"#:1st");
+    assert_debug_snapshot!(literal("#:with-label"), @r#"
+    Keyword(
+        Keyword {
+            name: "with-label",
+        },
+    )
+    "#);
+    assert_snapshot!(crate::parse_notation("#:").unwrap_err(), @"1:1: keyword `#:` has no name");
+    let err = crate::parse_notation("(f #: 1)").unwrap_err();
+    assert_eq!(err.kind, crate::ParseErrorKind::EmptyKeyword);
+    assert_snapshot!(err, @"1:4: keyword `#:` has no name");
+    assert_eq!(err.span.span, "#:");
+    assert_snapshot!(crate::parse_notation("(f #:x 1 #:y)").unwrap(), @"(f #:x 1 #:y)");
+}
+
+#[test]
+fn parse_byte_string() {
+    assert_snapshot!(literal(r#"#"abc""#), @r#"#"abc""#);
+    assert_snapshot!(literal(r#"#"\x0;\xFF;\n\"q\" \u{7f}""#), @r#"#"\x0;\xff;\n\"q\" \x7f;""#);
+    assert_debug_snapshot!(literal(r#"#"a\x80;""#), @r"
+    ByteString(
+        ByteString {
+            value: [
+                97,
+                128,
+            ],
+        },
+    )
+    ");
+    assert_snapshot!(crate::parse_notation(r#"#"\x100;""#).unwrap_err(), @r"1:1: invalid literal: `\x100;` is not a byte");
+    assert_snapshot!(crate::parse_notation(r#"#"é""#).unwrap_err(), @r"1:1: invalid literal: `é` is not ASCII, write its bytes as `\x..;` escapes");
+}
+
 #[test]
 fn parse_string() {
     assert_snapshot!(string("\"abc\""), @"\"abc\"");
@@ -125,3 +162,217 @@ fn parse_literal() {
     assert_snapshot!(literal("#\\c"), @"#\\c");
     assert_snapshot!(literal("\"abc\""), @"\"abc\"");
 }
+
+#[test]
+fn invalid_identifier_is_reported() {
+    let mut output = ENotationParser::parse(Rule::identifier, "789").unwrap();
+    let err = crate::convert::convert::<Identifier>(&mut output).unwrap_err();
+    assert_snapshot!(err.kind, @"invalid literal: `789` is a number, not an identifier");
+}
+
+#[test]
+fn parse_string_content() {
+    assert_snapshot!(string(r#""hello world""#), @r#""hello world""#);
+    assert_snapshot!(string(r#""a1""#), @r#""a1""#);
+    assert_snapshot!(string(r#""naïve 😀""#), @r#""naïve 😀""#);
+    assert_eq!(
+        string(r#""tab\tquote\"back\\slash""#).value,
+        "tab\tquote\"back\\slash"
+    );
+    assert_snapshot!(string(r#""\x41;\u{1F600}\|""#).value, @"A😀|");
+    assert_snapshot!(string("\"line one\nline two\"").value, @r"
+    line one
+    line two
+    ");
+    // line continuation drops the newline and surrounding indentation
+    assert_snapshot!(string("\"one \\  \n    two\"").value, @"one two");
+}
+
+#[test]
+fn print_string_reparses() {
+    for source in [r#""a\nb\"c\\d""#, "\"\\x7;\\x0;é\"", "\"\\r\\t\\a\\b\""] {
+        let printed = string(source).to_string();
+        assert_eq!(string(&printed).value, string(source).value);
+    }
+    assert_snapshot!(string("\"\\x0;\\n\"").to_string(), @r#""\x0;\n""#);
+}
+
+#[test]
+fn invalid_string_escape() {
+    let err = crate::parse_notation(r#""\x110000;""#).unwrap_err();
+    assert_snapshot!(err, @"1:1: invalid literal: `110000` is not a Unicode scalar value");
+    assert!(ENotationParser::parse(Rule::string, r#""\q""#).is_err());
+}
+
+#[test]
+fn parse_big_numbers() {
+    assert_snapshot!(integer("9223372036854775807"), @"9223372036854775807");
+    assert_snapshot!(integer("-9223372036854775808"), @"-9223372036854775808");
+    assert_snapshot!(integer("123456789012345678901234567890"), @"123456789012345678901234567890");
+    assert_snapshot!(integer("1_000_000_000_000_000_000_000"), @"1000000000000000000000");
+    assert_eq!(integer("9223372036854775807").as_i64(), Some(i64::MAX));
+    assert_eq!(integer("9223372036854775808").as_i64(), None);
+    assert_snapshot!(integer("9223372036854775808").as_bigint(), @"9223372036854775808");
+    assert_eq!(integer("-12").to_f64(), -12.0);
+}
+
+#[test]
+fn rational_is_normalized() {
+    assert_snapshot!(rational("4/6"), @"2/3");
+    assert_snapshot!(rational("-4/6"), @"-2/3");
+    assert_snapshot!(rational("6/3"), @"2/1");
+    assert_snapshot!(rational("1/123456789012345678901234567890"), @"1/123456789012345678901234567890");
+    assert_eq!(rational("6/3").as_i64(), Some(2));
+    assert_eq!(rational("1/3").as_bigint(), None);
+    assert_eq!(rational("1/4").to_f64(), 0.25);
+    assert_eq!(literal("1/2").to_f64(), Some(0.5));
+    assert_eq!(literal("2.0").as_i64(), None);
+    assert_eq!(float("2.0").as_i64(), Some(2));
+}
+
+#[test]
+fn radix_prefix() {
+    assert_snapshot!(integer("#xFF"), @"#xff");
+    assert_snapshot!(integer("#b1010"), @"#b1010");
+    assert_snapshot!(integer("#o755"), @"#o755");
+    assert_snapshot!(integer("#d42"), @"42");
+    assert_snapshot!(integer("#x-1_0"), @"#x-10");
+    assert_snapshot!(integer("#xffffffffffffffffffff"), @"#xffffffffffffffffffff");
+    assert_snapshot!(rational("#x-a/f"), @"#x-2/3");
+    assert_eq!(integer("#xFF").as_i64(), Some(255));
+    assert_eq!(integer("#b1010").as_i64(), Some(10));
+    assert_eq!(integer("#o755").as_i64(), Some(0o755));
+    assert_eq!(integer("#o755").radix, Radix::Octal);
+}
+
+#[test]
+fn exactness_prefix() {
+    assert_snapshot!(float("#e1.5"), @"#e1.5");
+    assert_snapshot!(rational("#i1/3"), @"#i1/3");
+    assert_snapshot!(integer("#x#e10"), @"#e#x10");
+    assert_snapshot!(integer("#E#X10"), @"#e#x10");
+    assert_eq!(float("#e1.5").exactness, Some(Exactness::Exact));
+    assert_eq!(rational("#i1/3").exactness, Some(Exactness::Inexact));
+    assert_eq!(integer("10").exactness, None);
+}
+
+#[test]
+fn float_exponent_and_special_values() {
+    assert_snapshot!(float("1e10"), @"10000000000.0");
+    assert_snapshot!(float("6.02e-23"), @"6.02e-23");
+    assert_snapshot!(float("1.5E+3"), @"1500.0");
+    assert_snapshot!(float(".5e1"), @"5.0");
+    assert_snapshot!(float("+inf.0"), @"+inf.0");
+    assert_snapshot!(float("-inf.0"), @"-inf.0");
+    assert_snapshot!(float("+nan.0"), @"+nan.0");
+    assert_snapshot!(float("-nan.0"), @"-nan.0");
+    assert_snapshot!(float("2.0"), @"2.0");
+    assert_snapshot!(float("1e100"), @"1e100");
+    assert_snapshot!(float("0.1"), @"0.1");
+}
+
+#[test]
+fn print_float_reparses() {
+    for value in [
+        0.1,
+        -0.0,
+        1e-7,
+        1e21,
+        6.02e-23,
+        f64::MAX,
+        f64::MIN_POSITIVE,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NAN,
+        -f64::NAN,
+    ] {
+        let printed = Float {
+            value,
+            exactness: None,
+        }
+        .to_string();
+        assert_eq!(
+            float(&printed).value.to_bits(),
+            value.to_bits(),
+            "{}",
+            printed
+        );
+    }
+}
+
+#[test]
+fn number_needs_delimiter() {
+    assert_debug_snapshot!(literal("5a"), @r#"
+    Identifier(
+        Identifier {
+            name: "5a",
+        },
+    )
+    "#);
+    assert_debug_snapshot!(literal("+inf.0x"), @r#"
+    Identifier(
+        Identifier {
+            name: "+inf.0x",
+        },
+    )
+    "#);
+    assert_debug_snapshot!(literal("1e"), @r#"
+    Identifier(
+        Identifier {
+            name: "1e",
+        },
+    )
+    "#);
+}
+
+fn complex(input: &str) -> Complex {
+    let mut output = ENotationParser::parse(Rule::complex, input).unwrap();
+    Complex::from_pest(&mut output).unwrap()
+}
+
+#[test]
+fn parse_complex() {
+    assert_snapshot!(complex("1+2i"), @"1+2i");
+    assert_snapshot!(complex("-3.5i"), @"0-3.5i");
+    assert_snapshot!(complex("1@0.5"), @"1@0.5");
+    assert_snapshot!(complex("1/2-3/4i"), @"1/2-3/4i");
+    assert_snapshot!(complex("+i"), @"0+1i");
+    assert_snapshot!(complex("1e3-i"), @"1000.0-1i");
+    assert_snapshot!(complex("1e-3+2e+3i"), @"0.001+2000.0i");
+    assert_snapshot!(complex("-inf.0+nan.0i"), @"-inf.0+nan.0i");
+    assert_snapshot!(complex("#e1+2i"), @"#e1+2i");
+    assert_eq!(complex("1@0").to_complex64(), num::Complex::new(1.0, 0.0));
+    assert_eq!(complex("2-i").to_complex64(), num::Complex::new(2.0, -1.0));
+}
+
+#[test]
+fn complex_does_not_steal_identifiers() {
+    assert_snapshot!(literal("i"), @"i");
+    assert_snapshot!(literal("+i-x"), @"+i-x");
+    assert_snapshot!(literal("-inf.0i"), @"0-inf.0i");
+    assert!(matches!(literal("+i-x"), Literal::Identifier(_)));
+    assert!(matches!(literal("+i"), Literal::Complex(_)));
+}
+
+#[test]
+fn char_names_and_code_points() {
+    assert_eq!(char("#\\nul").value, '\0');
+    assert_eq!(char("#\\alarm").value, '\u{7}');
+    assert_eq!(char("#\\backspace").value, '\u{8}');
+    assert_eq!(char("#\\delete").value, '\u{7f}');
+    assert_eq!(char("#\\escape").value, '\u{1b}');
+    assert_eq!(char("#\\x41").value, 'A');
+    assert_eq!(char("#\\u{1F600}").value, '😀');
+    assert_eq!(char("#\\x").value, 'x');
+    assert_snapshot!(char("#\\null"), @r"#\null");
+    assert_snapshot!(char("#\\x7"), @r"#\alarm");
+    assert_snapshot!(char("#\\x1"), @r"#\x1");
+    assert_snapshot!(char("#\\xA0"), @r"#\xa0");
+    assert_snapshot!(char("#\\u{1F600}"), @r"#\😀");
+}
+
+#[test]
+fn unknown_char_name() {
+    assert_snapshot!(crate::parse_notation("#\\abc").unwrap_err(), @r"1:1: unknown character name `#\abc`");
+    assert_snapshot!(crate::parse_notation("(#\\xyz)").unwrap_err(), @r"1:2: unknown character name `#\xyz`");
+}
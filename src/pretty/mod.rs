@@ -0,0 +1,600 @@
+//! A width-aware printer, in the style of Wadler's "A prettier printer".
+//!
+//! `Display` puts a whole notation on one line, `pretty` keeps a form on one line while it
+//! fits into the width and breaks it otherwise, indenting the body of forms like `define`
+//! and `let` as Racket does, and aligning the arguments of other calls. `print_tree` lays out
+//! a [syntax tree](crate::cst) the same way and keeps its comments.
+use std::collections::HashMap;
+
+use crate::{
+    container::{hash::HashTable, list::List, vector::Vector, Container},
+    cst::{SyntaxElement, SyntaxKind, SyntaxNode},
+    labeling::Labeling,
+    literal::Literal,
+    quoting::Quoting,
+    syntaxing::Syntaxing,
+    EFile, ENotation, ENotationBody,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// How `pretty` lays out notations.
+#[derive(Debug, Clone)]
+pub struct Style {
+    width: usize,
+    /// For a head like `define`, how many arguments stay on the line of the head, the rest is
+    /// the body and is indented by two.
+    body_indent: HashMap<String, usize>,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        let body_indent = [
+            ("begin", 0),
+            ("cond", 0),
+            ("case-lambda", 0),
+            ("define", 1),
+            ("define-syntax", 1),
+            ("define-values", 1),
+            ("define-syntax-rule", 1),
+            ("lambda", 1),
+            ("λ", 1),
+            ("let", 1),
+            ("let*", 1),
+            ("letrec", 1),
+            ("let-values", 1),
+            ("let*-values", 1),
+            ("parameterize", 1),
+            ("when", 1),
+            ("unless", 1),
+            ("case", 1),
+            ("match", 1),
+            ("for", 1),
+            ("for/list", 1),
+            ("with-handlers", 1),
+            ("syntax-rules", 1),
+            ("syntax-case", 2),
+            ("module", 2),
+        ]
+        .into_iter()
+        .map(|(head, n)| (head.to_string(), n))
+        .collect();
+        Self {
+            width: 80,
+            body_indent,
+        }
+    }
+}
+
+impl Style {
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Lays out forms headed by `head` with `args` arguments on the first line and the rest
+    /// as an indented body.
+    pub fn with_body_indent(mut self, head: &str, args: usize) -> Self {
+        self.body_indent.insert(head.to_string(), args);
+        self
+    }
+
+    pub fn print(&self, notation: &ENotation) -> String {
+        render(&self.doc(notation), self.width)
+    }
+
+    pub fn print_file(&self, file: &EFile) -> String {
+        let mut out = String::new();
+        for notation in &file.notations {
+            out.push_str(&self.print(notation));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Prints a file from its [lossless tree](crate::cst), like `print_file`, but keeps its
+    /// comments where they are, its atoms as they are written and a blank line between forms
+    /// where there is one.
+    pub fn print_tree(&self, root: &SyntaxNode) -> String {
+        let mut docs = vec![];
+        for (i, (breaks, item)) in self.tree_items(root).into_iter().enumerate() {
+            if i > 0 {
+                docs.push(match item.kind {
+                    ItemKind::Comment { inline: true, .. } => Doc::text(" "),
+                    _ if breaks > 1 => Doc::Concat(vec![Doc::HardLine, Doc::HardLine]),
+                    _ => Doc::HardLine,
+                });
+            }
+            docs.push(item.doc);
+        }
+        let mut out = render(&Doc::Concat(docs), self.width);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out
+    }
+
+    fn doc(&self, notation: &ENotation) -> Doc {
+        match &notation.body {
+            ENotationBody::Literal(l) => Doc::text(l),
+            ENotationBody::Container(Container::List(l)) => {
+                let (open, close) = match l {
+                    List::PL(_) => ("(", ")"),
+                    List::BL(_) => ("[", "]"),
+                };
+                let head = match l.elems().first().map(|e| &e.body) {
+                    Some(ENotationBody::Literal(Literal::Identifier(head))) => Some(&*head.name),
+                    _ => None,
+                };
+                let mut items = self.items(l.elems());
+                if let Some(tail) = l.tail() {
+                    items.push(Item::dot());
+                    items.push(Item::notation(self.doc(tail)));
+                }
+                self.list(open, head, items, close)
+            }
+            ENotationBody::Container(Container::Vector(Vector::PV(v))) => {
+                self.sequence("#(", self.items(&v.elems), ")")
+            }
+            ENotationBody::Container(Container::Vector(Vector::BV(v))) => {
+                self.sequence("#[", self.items(&v.elems), "]")
+            }
+            ENotationBody::Container(Container::Set(s)) => {
+                self.sequence("#{", self.items(&s.elems), "}")
+            }
+            ENotationBody::Container(Container::Object(o)) => self.object(
+                o.pairs
+                    .iter()
+                    .map(|pair| {
+                        Item::notation(Doc::Concat(vec![
+                            Doc::text(format!("{} : ", pair.key)),
+                            self.doc(&pair.value),
+                        ]))
+                    })
+                    .collect(),
+            ),
+            ENotationBody::Container(Container::Bytevector(b)) => Doc::text(b),
+            ENotationBody::Container(Container::Hash(h)) => self.hash_table(h),
+            ENotationBody::Quoting(q) => {
+                let (mark, value) = match q {
+                    Quoting::Quote(q) => ("'", &q.value),
+                    Quoting::QuasiQuote(q) => ("`", &q.value),
+                    Quoting::Unquote(q) => (",", &q.value),
+                    Quoting::UnquoteSplicing(q) => (",@", &q.value),
+                };
+                Doc::Concat(vec![Doc::text(mark), self.doc(value)])
+            }
+            ENotationBody::Syntaxing(s) => {
+                let (mark, value) = match s {
+                    Syntaxing::Syntax(s) => ("#'", &s.value),
+                    Syntaxing::QuasiSyntax(s) => ("#`", &s.value),
+                    Syntaxing::Unsyntax(s) => ("#,", &s.value),
+                    Syntaxing::UnsyntaxSplicing(s) => ("#,@", &s.value),
+                };
+                Doc::Concat(vec![Doc::text(mark), self.doc(value)])
+            }
+            ENotationBody::Labeling(Labeling::Label(l)) => Doc::Concat(vec![
+                Doc::text(format!("#{}=", l.label)),
+                self.doc(&l.value),
+            ]),
+            ENotationBody::Labeling(Labeling::LabelRef(r)) => Doc::text(r),
+            ENotationBody::Error(_) => Doc::text(&notation.span.span),
+        }
+    }
+
+    fn items(&self, elems: &[ENotation]) -> Vec<Item> {
+        elems
+            .iter()
+            .map(|elem| Item::notation(self.doc(elem)))
+            .collect()
+    }
+
+    /// The notations and comments of a node of a syntax tree, with the number of line breaks
+    /// in front of each.
+    fn tree_items(&self, node: &SyntaxNode) -> Vec<(usize, Item)> {
+        let mut items = vec![];
+        let mut breaks = 0;
+        for element in node.children_with_tokens() {
+            let kind = match element.kind() {
+                SyntaxKind::Whitespace => {
+                    breaks += element.to_string().matches('\n').count();
+                    continue;
+                }
+                SyntaxKind::Dot => ItemKind::Dot,
+                kind @ (SyntaxKind::LineComment
+                | SyntaxKind::BlockComment
+                | SyntaxKind::DatumComment) => ItemKind::Comment {
+                    ends_line: kind == SyntaxKind::LineComment,
+                    inline: breaks == 0 && !items.is_empty(),
+                },
+                kind if kind.is_notation() || kind == SyntaxKind::ObjectPair => ItemKind::Notation,
+                // delimiters and marks are printed by the form they belong to
+                _ => continue,
+            };
+            let doc = match &element {
+                SyntaxElement::Token(token) if token.kind() == SyntaxKind::LineComment => {
+                    Doc::Comment(token.text().trim_end().to_string())
+                }
+                SyntaxElement::Token(token) => Doc::text(token.text()),
+                SyntaxElement::Node(node) => self.tree_doc(node),
+            };
+            items.push((breaks, Item { kind, doc }));
+            breaks = 0;
+        }
+        items
+    }
+
+    fn tree_doc(&self, node: &SyntaxNode) -> Doc {
+        let open = node.first_token().map_or(String::new(), |t| t.to_string());
+        let close = node.last_token().map_or(String::new(), |t| t.to_string());
+        let items = || -> Vec<Item> {
+            self.tree_items(node)
+                .into_iter()
+                .map(|(_, item)| item)
+                .collect()
+        };
+        match node.kind() {
+            SyntaxKind::List => {
+                let first = node
+                    .children_with_tokens()
+                    .skip(1)
+                    .find(|e| e.kind() != SyntaxKind::Whitespace);
+                let head = match &first {
+                    Some(SyntaxElement::Token(t)) if t.kind() == SyntaxKind::Identifier => {
+                        Some(t.text())
+                    }
+                    _ => None,
+                };
+                self.list(&open, head, items(), &close)
+            }
+            SyntaxKind::Vector | SyntaxKind::Set | SyntaxKind::Hash => {
+                self.sequence(&open, items(), &close)
+            }
+            // bytes are short, they fill the line like `Display` prints them
+            SyntaxKind::Bytevector => {
+                let items = items();
+                let last = items.last().map(|item| item.kind);
+                Doc::group(Doc::align(Doc::Concat(vec![
+                    Doc::text(open),
+                    Doc::align(lines(items, None, || Doc::text(" "))),
+                    closer(last, &close),
+                ])))
+            }
+            SyntaxKind::Object => self.object(items()),
+            SyntaxKind::ObjectPair => {
+                let mut key = items();
+                let value = key.split_off(key.len().min(1));
+                Doc::Concat(vec![
+                    lines(key, None, || Doc::Line),
+                    Doc::text(" : "),
+                    lines(value, None, || Doc::text(" ")),
+                ])
+            }
+            // quotings, syntaxings, labels and `#;` comments, a mark and a notation
+            _ => Doc::Concat(vec![
+                Doc::text(open),
+                lines(items(), None, || Doc::text(" ")),
+            ]),
+        }
+    }
+
+    /// Elements aligned under the first one, for data.
+    fn sequence(&self, open: &str, items: Vec<Item>, close: &str) -> Doc {
+        let last = items.last().map(|item| item.kind);
+        Doc::group(Doc::align(Doc::Concat(vec![
+            Doc::text(open),
+            Doc::align(lines(items, None, || Doc::Line)),
+            closer(last, close),
+        ])))
+    }
+
+    /// A call, arguments are aligned under the first argument, or the body of a known head is
+    /// indented. `head` is the name of the first item, if it is an identifier. A dotted list
+    /// is data, its tail is aligned as one more element after the `.`.
+    fn list(&self, open: &str, head: Option<&str>, mut items: Vec<Item>, close: &str) -> Doc {
+        let head = match head {
+            Some(head) if items.iter().all(|item| item.kind != ItemKind::Dot) => head,
+            _ => return self.sequence(open, items, close),
+        };
+        let last = items.last().map(|item| item.kind);
+        let mut args = items.split_off(1);
+        let mut docs = vec![Doc::text(open)];
+        docs.extend(items.into_iter().map(|item| item.doc));
+        match self.body_indent.get(head) {
+            Some(&n) => {
+                // the first `n` notations stay on the line of the head, with the comments
+                // that follow them there
+                let mut split = match n {
+                    0 => 0,
+                    n => args
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, arg)| arg.kind == ItemKind::Notation)
+                        .nth(n - 1)
+                        .map_or(args.len(), |(i, _)| i + 1),
+                };
+                while args
+                    .get(split)
+                    .is_some_and(|arg| matches!(arg.kind, ItemKind::Comment { inline: true, .. }))
+                {
+                    split += 1;
+                }
+                let body = args.split_off(split);
+                let prev = args.last().map_or(ItemKind::Notation, |arg| arg.kind);
+                docs.push(lines(args, Some(ItemKind::Notation), || Doc::text(" ")));
+                docs.push(Doc::Nest(
+                    2,
+                    Box::new(lines(body, Some(prev), || Doc::Line)),
+                ));
+            }
+            None if args.is_empty() => {}
+            None => {
+                docs.push(Doc::text(" "));
+                docs.push(Doc::align(lines(args, None, || Doc::Line)));
+            }
+        }
+        docs.push(closer(last, close));
+        Doc::group(Doc::align(Doc::Concat(docs)))
+    }
+
+    /// Entries aligned under the first one, each printed as a dotted pair.
+    fn hash_table(&self, table: &HashTable) -> Doc {
+        let entries = table
+            .entries
+            .iter()
+            .map(|entry| {
+                let pair = vec![
+                    Item::notation(self.doc(&entry.key)),
+                    Item::dot(),
+                    Item::notation(self.doc(&entry.value)),
+                ];
+                Item::notation(self.sequence("(", pair, ")"))
+            })
+            .collect();
+        self.sequence(table.flavor.opener(), entries, ")")
+    }
+
+    /// The notations of `items` are its pairs.
+    fn object(&self, mut items: Vec<Item>) -> Doc {
+        if items.is_empty() {
+            return Doc::text("{}");
+        }
+        // a comma follows every pair but the last, before the comments after the pair
+        let pairs = items
+            .iter()
+            .filter(|item| item.kind == ItemKind::Notation)
+            .count();
+        for item in items
+            .iter_mut()
+            .filter(|item| item.kind == ItemKind::Notation)
+            .take(pairs.saturating_sub(1))
+        {
+            let pair = std::mem::replace(&mut item.doc, Doc::Concat(vec![]));
+            item.doc = Doc::Concat(vec![pair, Doc::text(",")]);
+        }
+        let close = match items.last().map(|item| item.kind) {
+            Some(ItemKind::Comment {
+                ends_line: true, ..
+            }) => Doc::HardLine,
+            _ => Doc::SoftLine,
+        };
+        Doc::group(Doc::Concat(vec![
+            Doc::text("{"),
+            Doc::Nest(
+                2,
+                Box::new(Doc::Concat(vec![
+                    Doc::SoftLine,
+                    lines(items, None, || Doc::Line),
+                ])),
+            ),
+            close,
+            Doc::text("}"),
+        ]))
+    }
+}
+
+/// What an element of a form is, it decides the separator in front of the next element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ItemKind {
+    Notation,
+    /// The `.` before the tail of a list.
+    Dot,
+    /// A comment of a syntax tree, a `;` comment ends its line, an `inline` one follows the
+    /// element before it on the same line.
+    Comment {
+        ends_line: bool,
+        inline: bool,
+    },
+}
+
+/// An element of a form, only syntax trees have comments between their notations.
+struct Item {
+    kind: ItemKind,
+    doc: Doc,
+}
+
+impl Item {
+    fn notation(doc: Doc) -> Self {
+        Item {
+            kind: ItemKind::Notation,
+            doc,
+        }
+    }
+
+    fn dot() -> Self {
+        Item {
+            kind: ItemKind::Dot,
+            doc: Doc::text("."),
+        }
+    }
+}
+
+/// Items one after the other, separated by `line()` unless a comment or a `.` needs another
+/// separator. `prev` is the element they follow on the line, if any.
+fn lines(items: Vec<Item>, mut prev: Option<ItemKind>, line: fn() -> Doc) -> Doc {
+    let mut docs = vec![];
+    for item in items {
+        match (prev, item.kind) {
+            (None, _) => {}
+            (
+                Some(ItemKind::Comment {
+                    ends_line: true, ..
+                }),
+                _,
+            ) => docs.push(Doc::HardLine),
+            (Some(ItemKind::Dot), _) | (_, ItemKind::Comment { inline: true, .. }) => {
+                docs.push(Doc::text(" "))
+            }
+            _ => docs.push(line()),
+        }
+        prev = Some(item.kind);
+        docs.push(item.doc);
+    }
+    Doc::Concat(docs)
+}
+
+/// The closer of a form, on a line of its own after a `;` comment.
+fn closer(last: Option<ItemKind>, close: &str) -> Doc {
+    match last {
+        Some(ItemKind::Comment {
+            ends_line: true, ..
+        }) => Doc::Concat(vec![Doc::HardLine, Doc::text(close)]),
+        _ => Doc::text(close),
+    }
+}
+
+impl ENotation {
+    /// Prints this notation within `width` columns where possible.
+    pub fn pretty(&self, width: usize) -> String {
+        Style::default().with_width(width).print(self)
+    }
+}
+
+impl EFile {
+    /// Prints every notation within `width` columns where possible, one after the other.
+    pub fn pretty(&self, width: usize) -> String {
+        Style::default().with_width(width).print_file(self)
+    }
+}
+
+enum Doc {
+    Text(String),
+    /// A `;` comment, it does not count towards the width of its line.
+    Comment(String),
+    /// A space, or a newline when the group is broken.
+    Line,
+    /// Nothing, or a newline when the group is broken.
+    SoftLine,
+    /// Always a newline, the groups around it are broken.
+    HardLine,
+    /// Indents the lines of a document further.
+    Nest(usize, Box<Doc>),
+    /// Indents the lines of a document to the column it starts at.
+    Align(Box<Doc>),
+    /// Either all lines of a document break or none does.
+    Group(Box<Doc>),
+    Concat(Vec<Doc>),
+}
+
+impl Doc {
+    fn text(text: impl ToString) -> Self {
+        Doc::Text(text.to_string())
+    }
+
+    fn align(doc: Doc) -> Self {
+        Doc::Align(Box::new(doc))
+    }
+
+    fn group(doc: Doc) -> Self {
+        Doc::Group(Box::new(doc))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+fn render(doc: &Doc, width: usize) -> String {
+    let mut out = String::new();
+    let mut column = 0;
+    let mut stack = vec![(0, Mode::Break, doc)];
+    while let Some((indent, mode, doc)) = stack.pop() {
+        match doc {
+            Doc::Text(text) | Doc::Comment(text) => {
+                out.push_str(text);
+                // a block comment or a string can span lines
+                column = match text.rsplit_once('\n') {
+                    Some((_, last)) => last.chars().count(),
+                    None => column + text.chars().count(),
+                };
+            }
+            Doc::Line | Doc::SoftLine | Doc::HardLine
+                if mode == Mode::Break || matches!(doc, Doc::HardLine) =>
+            {
+                out.push('\n');
+                out.extend(std::iter::repeat_n(' ', indent));
+                column = indent;
+            }
+            Doc::Line => {
+                out.push(' ');
+                column += 1;
+            }
+            Doc::SoftLine | Doc::HardLine => {}
+            Doc::Nest(n, doc) => stack.push((indent + n, mode, doc)),
+            Doc::Align(doc) => stack.push((column, mode, doc)),
+            Doc::Group(doc) => {
+                let flat = mode == Mode::Flat
+                    || fits(
+                        width as isize - column as isize,
+                        (indent, Mode::Flat, doc),
+                        &stack,
+                    );
+                stack.push((indent, if flat { Mode::Flat } else { Mode::Break }, doc));
+            }
+            Doc::Concat(docs) => {
+                for doc in docs.iter().rev() {
+                    stack.push((indent, mode, doc));
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Whether the rest of the line fits into `remaining` columns when `next` is laid out flat.
+fn fits(mut remaining: isize, next: (usize, Mode, &Doc), rest: &[(usize, Mode, &Doc)]) -> bool {
+    let mut stack = vec![next];
+    let mut rest = rest.iter().rev();
+    while remaining >= 0 {
+        let (indent, mode, doc) = match stack.pop() {
+            Some(item) => item,
+            None => match rest.next() {
+                Some(item) => *item,
+                None => return true,
+            },
+        };
+        match doc {
+            Doc::Text(text) => match text.split_once('\n') {
+                Some((first, _)) => return remaining >= first.chars().count() as isize,
+                None => remaining -= text.chars().count() as isize,
+            },
+            Doc::Comment(_) => {}
+            Doc::Line | Doc::SoftLine | Doc::HardLine if mode == Mode::Break => return true,
+            Doc::Line => remaining -= 1,
+            Doc::SoftLine => {}
+            Doc::HardLine => return false,
+            Doc::Nest(_, doc) | Doc::Align(doc) | Doc::Group(doc) => {
+                stack.push((indent, mode, doc))
+            }
+            Doc::Concat(docs) => {
+                for doc in docs.iter().rev() {
+                    stack.push((indent, mode, doc));
+                }
+            }
+        }
+    }
+    false
+}
@@ -0,0 +1,166 @@
+use insta::assert_snapshot;
+
+use super::Style;
+use crate::{cst, parse_file, parse_notation};
+
+#[test]
+fn short_forms_stay_on_one_line() {
+    let notation = parse_notation("(f   a [b  c] #(1 2) {a : 1})").unwrap();
+    assert_snapshot!(notation.pretty(80), @"(f a [b c] #(1 2) {a : 1})");
+}
+
+#[test]
+fn break_calls_and_data() {
+    let notation =
+        parse_notation("(define (fib n) (if (< n 2) n (+ (fib (- n 1)) (fib (- n 2)))))").unwrap();
+    assert_snapshot!(notation.pretty(40), @r"
+    (define (fib n)
+      (if (< n 2)
+          n
+          (+ (fib (- n 1)) (fib (- n 2)))))
+    ");
+    assert_snapshot!(notation.pretty(20), @r"
+    (define (fib n)
+      (if (< n 2)
+          n
+          (+ (fib (- n
+                     1))
+             (fib (- n
+                     2)))))
+    ");
+    let data = parse_notation("'(alpha beta gamma #(1 2 3 4 5 6) #{x y z})").unwrap();
+    assert_snapshot!(data.pretty(20), @r"
+    '(alpha beta
+            gamma
+            #(1
+              2
+              3
+              4
+              5
+              6)
+            #{x y z})
+    ");
+}
+
+#[test]
+fn break_dotted_lists() {
+    let notation = parse_notation("((alpha . 1) (beta . (gamma delta)))").unwrap();
+    assert_snapshot!(notation.pretty(80), @"((alpha . 1) (beta . (gamma delta)))");
+    assert_snapshot!(notation.pretty(12), @r"
+    ((alpha . 1)
+     (beta
+      . (gamma delta)))
+    ");
+}
+
+#[test]
+fn break_hash_tables() {
+    let notation = parse_notation("#hash((alpha . 1) (\"beta\" . (gamma delta)))").unwrap();
+    assert_snapshot!(notation.pretty(80), @r#"#hash((alpha . 1) ("beta" . (gamma delta)))"#);
+    assert_snapshot!(notation.pretty(20), @r#"
+    #hash((alpha . 1)
+          ("beta"
+           . (gamma delta)))
+    "#);
+}
+
+#[test]
+fn body_indent_rules() {
+    let file = parse_file(
+        "(let ([x 1] [y 2]) (displayln x) (displayln y))
+(lambda (x) x)",
+    )
+    .unwrap();
+    assert_snapshot!(file.pretty(20), @r"
+    (let ([x 1] [y 2])
+      (displayln x)
+      (displayln y))
+    (lambda (x) x)
+    ");
+    let notation = parse_notation("(my-form a b c)").unwrap();
+    assert_snapshot!(notation.pretty(10), @r"
+    (my-form a
+             b
+             c)
+    ");
+    let style = Style::default()
+        .with_width(10)
+        .with_body_indent("my-form", 1);
+    assert_snapshot!(style.print(&notation), @r"
+    (my-form a
+      b
+      c)
+    ");
+}
+
+#[test]
+fn break_objects() {
+    let notation = parse_notation("{name : \"enotation\", version : 2, tags : [a b c]}").unwrap();
+    let nested = parse_notation("(config {name : \"enotation\", version : 2})").unwrap();
+    assert_snapshot!(nested.pretty(20), @r#"
+    (config {
+              name : "enotation",
+              version : 2
+            })
+    "#);
+    assert_snapshot!(notation.pretty(30), @r#"
+    {
+      name : "enotation",
+      version : 2,
+      tags : [a b c]
+    }
+    "#);
+}
+
+#[test]
+fn pretty_output_reparses() {
+    let source = "(define (f x) `(g ,x ,@(h x) #'k) {a : [1 2 3], b : #{c}})";
+    let notation = parse_notation(source).unwrap();
+    for width in [0, 10, 30, 100] {
+        let printed = notation.pretty(width);
+        assert_eq!(
+            parse_notation(&printed).unwrap().to_string(),
+            notation.to_string()
+        );
+    }
+}
+
+#[test]
+fn print_tree_keeps_comments() {
+    let root = cst::parse(
+        "; config
+(define   x 1) ; trailing
+
+
+(let ([y 2]) ; why
+  ; own line
+  #| block |# y #;z)
+{a : 1, ; first
+ b : 2
+ ; last
+}
+(f ; c
+)",
+    )
+    .unwrap();
+    let printed = Style::default().with_width(20).print_tree(&root);
+    assert_snapshot!(printed, @r"
+    ; config
+    (define x 1) ; trailing
+
+    (let ([y 2]) ; why
+      ; own line
+      #| block |#
+      y #;z)
+    {
+      a : 1, ; first
+      b : 2
+      ; last
+    }
+    (f ; c
+    )
+    ");
+    let again = cst::parse(&printed).unwrap();
+    assert_eq!(Style::default().with_width(20).print_tree(&again), printed);
+    assert_eq!(cst::lower(&again), cst::lower(&root));
+}
@@ -1,12 +1,14 @@
-use crate::{ENotation, Rule, SetDebugFileName};
-use pest_ast::FromPest;
+use crate::{
+    convert::{from_pest, one_of},
+    ENotation, Rule,
+};
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 #[cfg(test)]
 mod tests;
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::quoting))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Quoting {
     Quote(Quote),
     QuasiQuote(QuasiQuote),
@@ -14,63 +16,40 @@ pub enum Quoting {
     UnquoteSplicing(UnquoteSplicing),
 }
 
-impl SetDebugFileName for Quoting {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        match self {
-            Quoting::Quote(quote) => quote.set_debug_file_name(file_name),
-            Quoting::QuasiQuote(quasi_quote) => quasi_quote.set_debug_file_name(file_name),
-            Quoting::Unquote(unquote) => unquote.set_debug_file_name(file_name),
-            Quoting::UnquoteSplicing(unquote_splicing) => unquote_splicing.set_debug_file_name(file_name),
-        }
-    }
-}
-
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::quote))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Quote {
     pub value: Box<ENotation>,
 }
 
-impl SetDebugFileName for Quote {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        self.value.set_debug_file_name(file_name);
-    }
-}
-
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::quasiquote))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct QuasiQuote {
     pub value: Box<ENotation>,
 }
 
-impl SetDebugFileName for QuasiQuote {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        self.value.set_debug_file_name(file_name);
-    }
-}
-
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::unquote))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Unquote {
     pub value: Box<ENotation>,
 }
 
-impl SetDebugFileName for Unquote {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        self.value.set_debug_file_name(file_name);
-    }
-}
-
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::unquote_splicing))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct UnquoteSplicing {
     pub value: Box<ENotation>,
 }
 
-impl SetDebugFileName for UnquoteSplicing {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        self.value.set_debug_file_name(file_name);
-    }
+from_pest! {
+    Quote = Rule::quote => |_, inner| Ok(Quote { value: Box::from_pest(inner)? });
+    QuasiQuote = Rule::quasiquote => |_, inner| Ok(QuasiQuote { value: Box::from_pest(inner)? });
+    Unquote = Rule::unquote => |_, inner| Ok(Unquote { value: Box::from_pest(inner)? });
+    UnquoteSplicing = Rule::unquote_splicing => |_, inner| Ok(UnquoteSplicing { value: Box::from_pest(inner)? });
+    Quoting = Rule::quoting => |_, inner| {
+        one_of!(
+            inner,
+            Quoting::Quote,
+            Quoting::QuasiQuote,
+            Quoting::Unquote,
+            Quoting::UnquoteSplicing,
+        )
+    };
 }
 
 impl Display for Quoting {
@@ -0,0 +1,217 @@
+use pest::Parser;
+
+use crate::{
+    check,
+    convert::convert,
+    error::{
+        delimiter::{Delimiter, Scan},
+        span_of,
+    },
+    parse_file,
+    visit_mut::VisitMut,
+    DiagnosticSpan, EFile, ENotation, ENotationBody, ENotationParser, Eoi, ErrorNode, ParseError,
+    ParseErrorKind, Rule,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Parses as much of `input` as possible, for editors and linters.
+///
+/// Every region that cannot be parsed becomes an `ENotationBody::Error` notation, and parsing
+/// continues at the end of the broken form or at the next top-level form, whichever the
+/// delimiters allow. The returned errors are in source order and empty iff `parse_file`
+/// would succeed.
+pub fn parse_file_recovering(input: &str) -> (EFile, Vec<ParseError>) {
+    if let Ok(file) = parse_file(input) {
+        return (file, vec![]);
+    }
+    let scan = Scan::new(input);
+    let mut notations = vec![];
+    let mut errors = vec![];
+    let mut origin = Origin::default();
+    loop {
+        origin.advance(input, skip_trivia(input, origin.offset));
+        let rest = &input[origin.offset..];
+        if rest.is_empty() {
+            break;
+        }
+        let end = match ENotationParser::parse(Rule::notation, rest) {
+            Ok(mut pairs) => {
+                let end = origin.offset + pairs.peek().map_or(0, |p| p.as_span().end());
+                let notation = convert::<ENotation>(&mut pairs)
+                    .and_then(|notation| check(&notation).map(|()| notation));
+                match notation {
+                    Ok(mut notation) => {
+                        Relocate(&origin).visit_notation_mut(&mut notation);
+                        notations.push(notation);
+                    }
+                    Err(mut err) => {
+                        origin.relocate(&mut err.span);
+                        if let Some(related) = &mut err.related {
+                            origin.relocate(related);
+                        }
+                        errors.push(err);
+                        notations.push(error_node(input, origin.offset, end));
+                    }
+                }
+                end
+            }
+            Err(err) => {
+                let mut err = ParseError::from_pest_with(rest, err, |start| {
+                    scan.check(origin.offset, origin.offset + start)
+                });
+                origin.relocate(&mut err.span);
+                if let Some(related) = &mut err.related {
+                    origin.relocate(related);
+                }
+                // an unterminated block comment swallows the rest of the input
+                let end = if err.kind == ParseErrorKind::UnterminatedComment {
+                    input.len()
+                } else {
+                    resync(input, &scan, origin.offset)
+                };
+                errors.push(err);
+                notations.push(error_node(input, origin.offset, end));
+                end
+            }
+        };
+        origin.advance(input, end);
+    }
+    (
+        EFile {
+            notations,
+            _eoi: Eoi {},
+        },
+        errors,
+    )
+}
+
+fn error_node(input: &str, start: usize, end: usize) -> ENotation {
+    let end = start + input[start..end].trim_end().len();
+    ENotation {
+        span: span_of(input, start, end),
+        body: ENotationBody::Error(ErrorNode),
+    }
+}
+
+fn skip_trivia(input: &str, from: usize) -> usize {
+    ENotationParser::parse(Rule::trivia, &input[from..])
+        .ok()
+        .and_then(|mut pairs| pairs.next())
+        .map_or(from, |pair| from + pair.as_span().end())
+}
+
+/// Finds where the broken form starting at `start` ends.
+fn resync(input: &str, scan: &Scan, start: usize) -> usize {
+    let rest = &input[start..];
+    let body = start + prefix_len(rest);
+    let mut delimiters = scan.from(body).iter().cloned();
+    match delimiters.next() {
+        // the form is a container, skip to its closer
+        Some((range, Delimiter::Open(_))) if range.start == body => {
+            let mut depth = 1;
+            for (range, delimiter) in delimiters {
+                match delimiter {
+                    Delimiter::Open(_) if starts_line(input, range.start) => return range.start,
+                    Delimiter::Open(_) => depth += 1,
+                    Delimiter::Close(_) => depth -= 1,
+                }
+                if depth == 0 {
+                    return range.end;
+                }
+            }
+            next_top_level(input, body)
+        }
+        // a closer without opener is an error on its own
+        Some((range, Delimiter::Close(_))) if range.start == body => range.end,
+        // a broken atom ends at the next delimiter or whitespace
+        _ => {
+            let atom = input[body..]
+                .find(|c: char| c.is_whitespace() || "()[]{}\"".contains(c))
+                .unwrap_or(input.len() - body);
+            if atom == 0 {
+                next_top_level(input, body)
+            } else {
+                body + atom
+            }
+        }
+    }
+}
+
+/// The length of the quoting and syntaxing marks in front of a notation.
+fn prefix_len(input: &str) -> usize {
+    let marks = ["#,@", ",@", "#'", "#`", "#,", "'", "`", ","];
+    let mut len = 0;
+    while let Some(mark) = marks.iter().find(|m| input[len..].starts_with(**m)) {
+        len += mark.len();
+    }
+    len
+}
+
+fn starts_line(input: &str, pos: usize) -> bool {
+    pos > 0 && input.as_bytes()[pos - 1] == b'\n'
+}
+
+/// A line that starts with an opener is most likely the next top-level form.
+fn next_top_level(input: &str, from: usize) -> usize {
+    let mut pos = from;
+    while let Some(newline) = input[pos..].find('\n') {
+        pos += newline + 1;
+        if input[pos..].starts_with(['(', '[', '{', '#']) {
+            return pos;
+        }
+    }
+    input.len()
+}
+
+/// Where a substring of the input starts, to move spans of a substring parse back into the
+/// whole input.
+#[derive(Debug, Clone)]
+struct Origin {
+    offset: usize,
+    line: usize,
+    col: usize,
+}
+
+impl Default for Origin {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            line: 1,
+            col: 1,
+        }
+    }
+}
+
+impl Origin {
+    fn advance(&mut self, input: &str, to: usize) {
+        let skipped = span_of(&input[self.offset..to], 0, to - self.offset);
+        (self.line, self.col) = self.locate(skipped.end_line, skipped.end_col);
+        self.offset = to;
+    }
+
+    fn locate(&self, line: usize, col: usize) -> (usize, usize) {
+        if line == 1 {
+            (self.line, self.col + col - 1)
+        } else {
+            (self.line + line - 1, col)
+        }
+    }
+
+    fn relocate(&self, span: &mut DiagnosticSpan) {
+        (span.start_line, span.start_col) = self.locate(span.start_line, span.start_col);
+        (span.end_line, span.end_col) = self.locate(span.end_line, span.end_col);
+        span.start_offset += self.offset;
+        span.end_offset += self.offset;
+    }
+}
+
+/// Moves the spans of a notation parsed from a substring to where it starts in the input.
+struct Relocate<'a>(&'a Origin);
+
+impl VisitMut for Relocate<'_> {
+    fn visit_span_mut(&mut self, node: &mut DiagnosticSpan) {
+        self.0.relocate(node);
+    }
+}
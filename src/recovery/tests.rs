@@ -0,0 +1,84 @@
+use insta::{assert_debug_snapshot, assert_snapshot};
+
+use super::parse_file_recovering;
+use crate::ENotationBody;
+
+#[test]
+fn recover_from_broken_forms() {
+    let (file, errors) = parse_file_recovering(
+        "(define x 1)
+(define y (f 1]
+(define z 2)
+(g 1/0)
+)
+'(h
+(define w 3)
+",
+    );
+    assert_snapshot!(file, @r"
+    (define x 1)
+    (define y (f 1]
+    (define z 2)
+    (g 1/0)
+    )
+    '(h
+    (define w 3)
+    ");
+    let opener = errors[0].related.as_ref().unwrap();
+    assert_eq!((opener.start_line, opener.start_col), (2, 11));
+    let errors = errors.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+    assert_debug_snapshot!(errors, @r#"
+    [
+        "2:15: mismatched closing delimiter `]` for `(`",
+        "4:4: rational literal has a zero denominator",
+        "5:1: unexpected closing delimiter `)`",
+        "8:1: unclosed delimiter `(`",
+    ]
+    "#);
+}
+
+#[test]
+fn error_node_covers_bad_region() {
+    let (file, errors) = parse_file_recovering("a\n  #abc b");
+    assert_eq!(errors.len(), 1);
+    let bad = &file.notations[1];
+    assert!(matches!(bad.body, ENotationBody::Error(_)));
+    assert_debug_snapshot!(
+        (&bad.span.span, bad.span.start_line, bad.span.start_col, bad.span.start_offset),
+        @r##"
+    (
+        "#abc",
+        2,
+        3,
+        4,
+    )
+    "##
+    );
+    // spans after the error still point into the whole input
+    let after = &file.notations[2];
+    assert_debug_snapshot!((after.span.start_line, after.span.start_col, after.span.start_offset), @r"
+    (
+        2,
+        8,
+        9,
+    )
+    ");
+}
+
+#[test]
+fn no_error_no_recovery() {
+    let (file, errors) = parse_file_recovering("(1 2) 3");
+    assert!(errors.is_empty());
+    assert_snapshot!(file, @r"
+    (1 2)
+    3
+    ");
+}
+
+#[test]
+fn unterminated_comment_swallows_the_rest() {
+    let (file, errors) = parse_file_recovering("(a)\n#| (b)\n(c)");
+    assert_eq!(file.notations.len(), 2);
+    assert!(matches!(file.notations[1].body, ENotationBody::Error(_)));
+    assert_snapshot!(errors[0], @"2:1: unterminated block comment");
+}
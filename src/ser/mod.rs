@@ -0,0 +1,500 @@
+//! Serializes Rust values to e-notation with serde, the counterpart of [`de`](crate::de).
+//!
+//! Structs and maps become objects, a map with keys that cannot be object keys a `#hash`
+//! table, sequences and tuples vectors, bytes a `#u8(...)` bytevector, `None` and `()` the
+//! nil form `()`, and enum variants their name or a list tagged with it.
+//!
+//! `Some` is written as its value, so `Some(())` and `Some(None)` are `()` as well and read
+//! back as `None`: an `Option<()>` or `Option<Option<T>>` does not round-trip.
+use num::BigInt;
+use serde::{ser, Serialize};
+
+pub use crate::de::Error;
+use crate::{
+    container::{
+        bytevector::Bytevector,
+        hash::{HashEntry, HashFlavor, HashTable},
+        list::{List, PList},
+        object::{Object, ObjectPair},
+        vector::{PVector, Vector},
+        Container,
+    },
+    literal::{Boolean, Char, Float, Identifier, Integer, IntegerValue, Literal, String_},
+    DiagnosticSpan, ENotation, ENotationBody,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Writes `value` on one line.
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, Error> {
+    Ok(to_notation(value)?.to_string())
+}
+
+/// Writes `value` with [`pretty`](ENotation::pretty), breaking lines at 80 columns.
+pub fn to_string_pretty<T: Serialize + ?Sized>(value: &T) -> Result<String, Error> {
+    Ok(to_notation(value)?.pretty(80))
+}
+
+/// Builds the notation of `value`, every span is empty.
+pub fn to_notation<T: Serialize + ?Sized>(value: &T) -> Result<ENotation, Error> {
+    value.serialize(Serializer)
+}
+
+impl ser::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Error {
+            message: msg.to_string(),
+            span: None,
+        }
+    }
+}
+
+fn notation(body: ENotationBody) -> ENotation {
+    ENotation {
+        span: DiagnosticSpan::default(),
+        body,
+    }
+}
+
+fn literal(literal: Literal) -> ENotation {
+    notation(ENotationBody::Literal(literal))
+}
+
+fn integer(value: IntegerValue) -> ENotation {
+    literal(Literal::Int(Integer {
+        value,
+        radix: Default::default(),
+        exactness: None,
+    }))
+}
+
+fn big(value: impl Into<BigInt>) -> ENotation {
+    let value = value.into();
+    integer(match i64::try_from(&value) {
+        Ok(value) => IntegerValue::Small(value),
+        Err(_) => IntegerValue::Big(value),
+    })
+}
+
+fn variant(name: &str) -> Result<ENotation, Error> {
+    Identifier::from_symbol(name)
+        .map(|name| literal(Literal::Identifier(name)))
+        .ok_or_else(|| ser::Error::custom(format!("`{}` cannot be a variant name", name)))
+}
+
+fn nil() -> ENotation {
+    notation(ENotationBody::Container(Container::List(List::PL(PList {
+        elems: vec![],
+        tail: None,
+    }))))
+}
+
+fn vector(elems: Vec<ENotation>) -> ENotation {
+    notation(ENotationBody::Container(Container::Vector(Vector::PV(
+        PVector { elems },
+    ))))
+}
+
+/// `(variant args...)`
+fn tagged(name: &str, mut args: Vec<ENotation>) -> Result<ENotation, Error> {
+    args.insert(0, variant(name)?);
+    Ok(notation(ENotationBody::Container(Container::List(
+        List::PL(PList {
+            elems: args,
+            tail: None,
+        }),
+    ))))
+}
+
+fn key(name: &str) -> Result<Identifier, Error> {
+    Identifier::from_symbol(name)
+        .ok_or_else(|| ser::Error::custom(format!("`{}` cannot be an object key", name)))
+}
+
+/// A serde serializer building a notation.
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = ENotation;
+    type Error = Error;
+
+    type SerializeSeq = SerializeVector;
+    type SerializeTuple = SerializeVector;
+    type SerializeTupleStruct = SerializeVector;
+    type SerializeTupleVariant = SerializeVector;
+    type SerializeMap = SerializeObject;
+    type SerializeStruct = SerializeObject;
+    type SerializeStructVariant = SerializeObject;
+
+    fn serialize_bool(self, v: bool) -> Result<ENotation, Error> {
+        Ok(literal(Literal::Boolean(Boolean { value: v })))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<ENotation, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<ENotation, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<ENotation, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<ENotation, Error> {
+        Ok(integer(IntegerValue::Small(v)))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<ENotation, Error> {
+        Ok(big(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<ENotation, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<ENotation, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<ENotation, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<ENotation, Error> {
+        Ok(big(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<ENotation, Error> {
+        Ok(big(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<ENotation, Error> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<ENotation, Error> {
+        Ok(literal(Literal::Float(Float {
+            value: v,
+            exactness: None,
+        })))
+    }
+
+    fn serialize_char(self, v: char) -> Result<ENotation, Error> {
+        Ok(literal(Literal::Char(Char { value: v })))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<ENotation, Error> {
+        Ok(literal(Literal::String_(String_ {
+            value: v.to_string(),
+        })))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<ENotation, Error> {
+        Ok(notation(ENotationBody::Container(Container::Bytevector(
+            Bytevector { bytes: v.to_vec() },
+        ))))
+    }
+
+    fn serialize_none(self) -> Result<ENotation, Error> {
+        Ok(nil())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<ENotation, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<ENotation, Error> {
+        Ok(nil())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<ENotation, Error> {
+        Ok(nil())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<ENotation, Error> {
+        self::variant(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<ENotation, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<ENotation, Error> {
+        tagged(variant, vec![value.serialize(self)?])
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeVector, Error> {
+        Ok(SerializeVector {
+            variant: None,
+            elems: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeVector, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeVector, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVector, Error> {
+        Ok(SerializeVector {
+            variant: Some(variant),
+            elems: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeObject, Error> {
+        Ok(SerializeObject {
+            variant: None,
+            pairs: vec![],
+            entries: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeObject, Error> {
+        Ok(SerializeObject {
+            variant: None,
+            pairs: Vec::with_capacity(len),
+            entries: vec![],
+            key: None,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeObject, Error> {
+        Ok(SerializeObject {
+            variant: Some(variant),
+            pairs: Vec::with_capacity(len),
+            entries: vec![],
+            key: None,
+        })
+    }
+}
+
+/// A vector, or a tagged list for a tuple variant.
+pub struct SerializeVector {
+    variant: Option<&'static str>,
+    elems: Vec<ENotation>,
+}
+
+impl SerializeVector {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.elems.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<ENotation, Error> {
+        match self.variant {
+            Some(variant) => tagged(variant, self.elems),
+            None => Ok(vector(self.elems)),
+        }
+    }
+}
+
+impl ser::SerializeSeq for SerializeVector {
+    type Ok = ENotation;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<ENotation, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SerializeVector {
+    type Ok = ENotation;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<ENotation, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeVector {
+    type Ok = ENotation;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<ENotation, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeVector {
+    type Ok = ENotation;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<ENotation, Error> {
+        self.finish()
+    }
+}
+
+/// An object, or a list tagged with a struct variant holding one. The entries of a map are
+/// only known to fit into an object once they are all serialized.
+pub struct SerializeObject {
+    variant: Option<&'static str>,
+    pairs: Vec<ObjectPair>,
+    entries: Vec<HashEntry>,
+    key: Option<ENotation>,
+}
+
+/// The object key of a map key that is a string, a char or an identifier.
+fn object_key(key: &ENotation) -> Option<Identifier> {
+    match &key.body {
+        ENotationBody::Literal(Literal::String_(s)) => Identifier::from_symbol(&s.value),
+        ENotationBody::Literal(Literal::Char(c)) => Identifier::from_symbol(&c.value.to_string()),
+        ENotationBody::Literal(Literal::Identifier(i)) => Identifier::from_symbol(i.symbol()),
+        _ => None,
+    }
+}
+
+impl SerializeObject {
+    fn push<T: Serialize + ?Sized>(&mut self, key: Identifier, value: &T) -> Result<(), Error> {
+        self.pairs.push(ObjectPair {
+            key,
+            value: value.serialize(Serializer)?,
+        });
+        Ok(())
+    }
+
+    fn finish(self) -> Result<ENotation, Error> {
+        let object = notation(ENotationBody::Container(Container::Object(Object {
+            pairs: self.pairs,
+        })));
+        match self.variant {
+            Some(variant) => tagged(variant, vec![object]),
+            None => Ok(object),
+        }
+    }
+}
+
+impl ser::SerializeMap for SerializeObject {
+    type Ok = ENotation;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(key.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self.key.take().expect("serde gives a key first");
+        self.entries.push(HashEntry {
+            key,
+            value: value.serialize(Serializer)?,
+        });
+        Ok(())
+    }
+
+    fn end(self) -> Result<ENotation, Error> {
+        let keys: Option<Vec<_>> = self
+            .entries
+            .iter()
+            .map(|entry| object_key(&entry.key))
+            .collect();
+        match keys {
+            Some(keys) => Ok(notation(ENotationBody::Container(Container::Object(
+                Object {
+                    pairs: keys
+                        .into_iter()
+                        .zip(self.entries)
+                        .map(|(key, entry)| ObjectPair {
+                            key,
+                            value: entry.value,
+                        })
+                        .collect(),
+                },
+            )))),
+            None => Ok(notation(ENotationBody::Container(Container::Hash(
+                HashTable {
+                    flavor: HashFlavor::Equal,
+                    entries: self.entries,
+                },
+            )))),
+        }
+    }
+}
+
+impl ser::SerializeStruct for SerializeObject {
+    type Ok = ENotation;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push(key(name)?, value)
+    }
+
+    fn end(self) -> Result<ENotation, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeObject {
+    type Ok = ENotation;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push(key(name)?, value)
+    }
+
+    fn end(self) -> Result<ENotation, Error> {
+        self.finish()
+    }
+}
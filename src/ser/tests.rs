@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+
+use insta::assert_snapshot;
+use serde::{Deserialize, Serialize};
+
+use crate::{from_str, parse_notation, to_string, to_string_pretty};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Server {
+    name: String,
+    port: u16,
+    tags: Vec<String>,
+    backup: Option<Box<Server>>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Rect(u32, u32),
+    Labeled { label: char, shape: Box<Shape> },
+}
+
+fn server() -> Server {
+    Server {
+        name: "main \"eu\"".to_string(),
+        port: 80,
+        tags: vec!["a".to_string(), "b".to_string()],
+        backup: Some(Box::new(Server {
+            name: "spare".to_string(),
+            port: 8080,
+            tags: vec![],
+            backup: None,
+        })),
+    }
+}
+
+fn shapes() -> Vec<Shape> {
+    vec![
+        Shape::Empty,
+        Shape::Circle(1.5),
+        Shape::Rect(16, 2),
+        Shape::Labeled {
+            label: ' ',
+            shape: Box::new(Shape::Circle(-0.0)),
+        },
+    ]
+}
+
+#[test]
+fn serialize() {
+    let mut map = BTreeMap::new();
+    map.insert("port number", (true, u64::MAX, i128::MIN));
+    assert_snapshot!(to_string(&server()).unwrap(), @r#"{name : "main \"eu\"", port : 80, tags : #("a" "b"), backup : {name : "spare", port : 8080, tags : #(), backup : ()}}"#);
+    assert_snapshot!(to_string(&shapes()).unwrap(), @r"#(Empty (Circle 1.5) (Rect 16 2) (Labeled {label : #\space, shape : (Circle -0.0)}))");
+    assert_snapshot!(to_string(&map).unwrap(), @"{|port number| : #(#t 18446744073709551615 -170141183460469231731687303715884105728)}");
+    assert_snapshot!(to_string_pretty(&server()).unwrap(), @r#"
+    {
+      name : "main \"eu\"",
+      port : 80,
+      tags : #("a" "b"),
+      backup : {name : "spare", port : 8080, tags : #(), backup : ()}
+    }
+    "#);
+}
+
+#[test]
+fn output_reparses() {
+    let line = to_string(&server()).unwrap();
+    let pretty = to_string_pretty(&server()).unwrap();
+    assert_eq!(parse_notation(&line).unwrap().to_string(), line);
+    assert_eq!(parse_notation(&pretty).unwrap().to_string(), line);
+    assert_eq!(
+        from_str::<Server>(&to_string_pretty(&server()).unwrap()).unwrap(),
+        server()
+    );
+    assert_eq!(
+        from_str::<Vec<Shape>>(&to_string(&shapes()).unwrap()).unwrap(),
+        shapes()
+    );
+    let mut map = BTreeMap::new();
+    map.insert("port number".to_string(), (true, u64::MAX, i128::MIN));
+    assert_eq!(
+        from_str::<BTreeMap<_, _>>(&to_string(&map).unwrap()).unwrap(),
+        map
+    );
+}
+
+#[test]
+fn other_keys_are_hash_tables() {
+    let map = BTreeMap::from([(1, "one".to_string()), (20, "twenty".to_string())]);
+    let esn = to_string(&map).unwrap();
+    assert_snapshot!(esn, @r#"#hash((1 . "one") (20 . "twenty"))"#);
+    assert_eq!(from_str::<BTreeMap<u32, String>>(&esn).unwrap(), map);
+    let map = BTreeMap::from([("a|b".to_string(), 1)]);
+    let esn = to_string(&map).unwrap();
+    assert_snapshot!(esn, @r#"#hash(("a|b" . 1))"#);
+    assert_eq!(from_str::<BTreeMap<String, i32>>(&esn).unwrap(), map);
+}
+
+struct Blob(&'static [u8]);
+
+impl Serialize for Blob {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+#[test]
+fn bytes_are_bytevectors() {
+    assert_snapshot!(to_string(&Blob(b"\x00\x01\xff")).unwrap(), @"#u8(0 1 255)");
+    assert_eq!(
+        from_str::<Vec<u8>>(&to_string(&Blob(b"hi")).unwrap()).unwrap(),
+        b"hi"
+    );
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Renamed {
+    #[serde(rename = "two words")]
+    TwoWords,
+    #[serde(rename = "1st")]
+    First(u8),
+    #[serde(rename = "a|b")]
+    Bar,
+}
+
+#[test]
+fn variant_names_are_symbols() {
+    let esn = to_string(&[Renamed::TwoWords, Renamed::First(1)]).unwrap();
+    assert_snapshot!(esn, @"#(|two words| (1st 1))");
+    assert_eq!(
+        from_str::<Vec<Renamed>>(&esn).unwrap(),
+        [Renamed::TwoWords, Renamed::First(1)]
+    );
+    assert_snapshot!(to_string(&Renamed::Bar).unwrap_err(), @"`a|b` cannot be a variant name");
+}
@@ -1,60 +1,34 @@
-use crate::{ENotation, Rule, SetDebugFileName};
-use pest_ast::FromPest;
+use crate::{
+    convert::{from_pest, one_of},
+    ENotation, Rule,
+};
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 #[cfg(test)]
 mod tests;
 
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::syntax))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Syntax {
     pub value: Box<ENotation>,
 }
 
-impl SetDebugFileName for Syntax {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        self.value.set_debug_file_name(file_name);
-    }
-}
-
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::quasisyntax))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct QuasiSyntax {
     pub value: Box<ENotation>,
 }
 
-impl SetDebugFileName for QuasiSyntax {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        self.value.set_debug_file_name(file_name);
-    }
-}
-
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::unsyntax))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Unsyntax {
     pub value: Box<ENotation>,
 }
 
-impl SetDebugFileName for Unsyntax {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        self.value.set_debug_file_name(file_name);
-    }
-}
-
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::unsyntax_splicing))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct UnsyntaxSplicing {
     pub value: Box<ENotation>,
 }
 
-impl SetDebugFileName for UnsyntaxSplicing {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        self.value.set_debug_file_name(file_name);
-    }
-}
-
-#[derive(Debug, Clone, FromPest)]
-#[pest_ast(rule(Rule::syntaxing))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Syntaxing {
     Syntax(Syntax),
     QuasiSyntax(QuasiSyntax),
@@ -62,17 +36,20 @@ pub enum Syntaxing {
     UnsyntaxSplicing(UnsyntaxSplicing),
 }
 
-impl SetDebugFileName for Syntaxing {
-    fn set_debug_file_name(&mut self, file_name: &str) {
-        match self {
-            Syntaxing::Syntax(syntax) => syntax.set_debug_file_name(file_name),
-            Syntaxing::QuasiSyntax(quasi_syntax) => quasi_syntax.set_debug_file_name(file_name),
-            Syntaxing::Unsyntax(unsyntax) => unsyntax.set_debug_file_name(file_name),
-            Syntaxing::UnsyntaxSplicing(unsyntax_splicing) => {
-                unsyntax_splicing.set_debug_file_name(file_name)
-            }
-        }
-    }
+from_pest! {
+    Syntax = Rule::syntax => |_, inner| Ok(Syntax { value: Box::from_pest(inner)? });
+    QuasiSyntax = Rule::quasisyntax => |_, inner| Ok(QuasiSyntax { value: Box::from_pest(inner)? });
+    Unsyntax = Rule::unsyntax => |_, inner| Ok(Unsyntax { value: Box::from_pest(inner)? });
+    UnsyntaxSplicing = Rule::unsyntax_splicing => |_, inner| Ok(UnsyntaxSplicing { value: Box::from_pest(inner)? });
+    Syntaxing = Rule::syntaxing => |_, inner| {
+        one_of!(
+            inner,
+            Syntaxing::Syntax,
+            Syntaxing::QuasiSyntax,
+            Syntaxing::Unsyntax,
+            Syntaxing::UnsyntaxSplicing,
+        )
+    };
 }
 
 impl Display for Syntaxing {
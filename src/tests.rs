@@ -1,4 +1,4 @@
-use crate::{EFile, ENotation, ENotationParser, Rule};
+use crate::{parse_file, parse_notation, EFile, ENotation, ENotationParser, Rule, Versioned};
 use from_pest::FromPest;
 use pest::Parser;
 
@@ -11,7 +11,7 @@ fn notation(input: &str) -> ENotation {
 fn all(input: &str) -> EFile {
     let mut output = ENotationParser::parse(Rule::file, input).unwrap();
     match EFile::from_pest(&mut output) {
-        Ok(f) => return f,
+        Ok(f) => f,
         Err(err) => panic!("{}", err),
     }
 }
@@ -46,6 +46,16 @@ fn parse_all() {
     ");
 }
 
+#[test]
+fn parse_entry_points() {
+    assert_snapshot!(parse_notation("  (1 2) ; trailing").unwrap(), @"(1 2)");
+    assert_snapshot!(parse_file("1 #t abc").unwrap(), @r"
+    1
+    #t
+    abc
+    ");
+}
+
 #[test]
 fn parse_comment() {
     let output = ENotationParser::parse(Rule::COMMENT, "; this is a comment")
@@ -56,8 +66,34 @@ fn parse_comment() {
     let output = ENotationParser::parse(Rule::COMMENT, "#;1").unwrap().peek();
     assert_debug_snapshot!(output, @"None");
 
+    let output = ENotationParser::parse(Rule::COMMENT, "#| a #| nested |# comment |#")
+        .unwrap()
+        .peek();
+    assert_debug_snapshot!(output, @"None");
+    assert_snapshot!(all("1 #| 2 #| 3 |# ) |# 4"), @r"
+    1
+    4
+    ");
+
     // let output = ENotationParser::parse(Rule::COMMENT, "#;(1 2 3)")
     //     .unwrap()
     //     .peek();
     // assert_debug_snapshot!(output, @"None");
 }
+
+#[test]
+fn serde_round_trip() {
+    let file = parse_file(
+        r#"(define x #e1.5) #(#\space "s" 1/2 #x-ff 1+2i) #{a} {k : 'b} #`(#,c)
+        12345678901234567890123 #0=(#0#) [a . b]"#,
+    )
+    .unwrap();
+    let json = serde_json::to_string(&Versioned::new(&file)).unwrap();
+    let back: Versioned<EFile> = serde_json::from_str(&json).unwrap();
+    assert_eq!(format!("{:?}", back.tree), format!("{:?}", file));
+
+    let json = serde_json::to_string(&notation("x")).unwrap();
+    assert_snapshot!(json, @r#"{"span":{"start_line":1,"start_col":1,"start_offset":0,"end_line":1,"end_col":2,"end_offset":1,"span":"x","file":null},"body":{"Literal":{"Identifier":{"name":"x"}}}}"#);
+    let old = format!(r#"{{"version": 0, "tree": {}}}"#, json);
+    assert_snapshot!(serde_json::from_str::<Versioned<ENotation>>(&old).unwrap_err(), @"AST version 0 is not supported, expected 6 at line 1 column 13");
+}
@@ -0,0 +1,419 @@
+//! Plain data without spans or bracket styles.
+//!
+//! [`Value`] is what a notation means rather than how it is written: `(a b)` and `[a b]` are
+//! the same list, `|a|` is the symbol `a`, and numbers are normalized by their exactness, so
+//! `#e1.5` is the rational `3/2`, `4/2` the integer `2` and `#i1` the float `1.0`. A dotted
+//! tail that is a list continues the list, `(a . (b))` is `(a b)`.
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use num::{BigInt, BigRational, FromPrimitive};
+
+use crate::{
+    container::{
+        bytevector::Bytevector,
+        hash::{HashEntry, HashFlavor, HashTable},
+        list::{List, PList},
+        object::{Object, ObjectPair},
+        set::Set,
+        vector::{PVector, Vector},
+        Container,
+    },
+    labeling::Labeling,
+    literal::{
+        Boolean, Char, Complex, ComplexValue, Exactness, Float, Identifier, Integer, IntegerValue,
+        Keyword, Literal, Rational, Real, String_,
+    },
+    parse_notation,
+    quoting::{QuasiQuote, Quote, Quoting, Unquote, UnquoteSplicing},
+    syntaxing::{QuasiSyntax, Syntax, Syntaxing, Unsyntax, UnsyntaxSplicing},
+    DiagnosticSpan, ENotation, ENotationBody, ParseError, ParseErrorKind,
+};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Char(char),
+    Int(BigInt),
+    Rational(BigRational),
+    Float(f64),
+    Complex(num::Complex<f64>),
+    String(String),
+    Symbol(String),
+    /// A `#:name` keyword, without the `#:`.
+    Keyword(String),
+    /// A byte string or bytevector.
+    Bytes(Vec<u8>),
+    List(Vec<Value>),
+    /// An improper list `(a b . c)`, its tail is never a list.
+    DottedList(Vec<Value>, Box<Value>),
+    Vector(Vec<Value>),
+    Set(Vec<Value>),
+    /// The pairs of an object in the order they are written.
+    Map(Vec<(String, Value)>),
+    /// The entries of a hash table in the order they are written.
+    Hash(HashFlavor, Vec<(Value, Value)>),
+    Quoted(QuoteKind, Box<Value>),
+}
+
+/// The quoting and syntaxing forms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuoteKind {
+    /// `'x`
+    Quote,
+    /// `` `x ``
+    QuasiQuote,
+    /// `,x`
+    Unquote,
+    /// `,@x`
+    UnquoteSplicing,
+    /// `#'x`
+    Syntax,
+    /// ``#`x``
+    QuasiSyntax,
+    /// `#,x`
+    Unsyntax,
+    /// `#,@x`
+    UnsyntaxSplicing,
+}
+
+impl QuoteKind {
+    /// The quoting or syntaxing of `value` this kind stands for.
+    pub(crate) fn wrap(self, value: Box<ENotation>) -> ENotationBody {
+        match self {
+            QuoteKind::Quote => ENotationBody::Quoting(Quoting::Quote(Quote { value })),
+            QuoteKind::QuasiQuote => {
+                ENotationBody::Quoting(Quoting::QuasiQuote(QuasiQuote { value }))
+            }
+            QuoteKind::Unquote => ENotationBody::Quoting(Quoting::Unquote(Unquote { value })),
+            QuoteKind::UnquoteSplicing => {
+                ENotationBody::Quoting(Quoting::UnquoteSplicing(UnquoteSplicing { value }))
+            }
+            QuoteKind::Syntax => ENotationBody::Syntaxing(Syntaxing::Syntax(Syntax { value })),
+            QuoteKind::QuasiSyntax => {
+                ENotationBody::Syntaxing(Syntaxing::QuasiSyntax(QuasiSyntax { value }))
+            }
+            QuoteKind::Unsyntax => {
+                ENotationBody::Syntaxing(Syntaxing::Unsyntax(Unsyntax { value }))
+            }
+            QuoteKind::UnsyntaxSplicing => {
+                ENotationBody::Syntaxing(Syntaxing::UnsyntaxSplicing(UnsyntaxSplicing { value }))
+            }
+        }
+    }
+
+    /// The kind of a quoting and the notation it quotes.
+    pub(crate) fn of_quoting(quoting: &Quoting) -> (Self, &ENotation) {
+        match quoting {
+            Quoting::Quote(q) => (QuoteKind::Quote, &q.value),
+            Quoting::QuasiQuote(q) => (QuoteKind::QuasiQuote, &q.value),
+            Quoting::Unquote(q) => (QuoteKind::Unquote, &q.value),
+            Quoting::UnquoteSplicing(q) => (QuoteKind::UnquoteSplicing, &q.value),
+        }
+    }
+
+    /// The kind of a syntaxing and the notation it quotes.
+    pub(crate) fn of_syntaxing(syntaxing: &Syntaxing) -> (Self, &ENotation) {
+        match syntaxing {
+            Syntaxing::Syntax(s) => (QuoteKind::Syntax, &s.value),
+            Syntaxing::QuasiSyntax(s) => (QuoteKind::QuasiSyntax, &s.value),
+            Syntaxing::Unsyntax(s) => (QuoteKind::Unsyntax, &s.value),
+            Syntaxing::UnsyntaxSplicing(s) => (QuoteKind::UnsyntaxSplicing, &s.value),
+        }
+    }
+}
+
+impl Value {
+    /// The empty list `()`.
+    pub fn nil() -> Self {
+        Value::List(vec![])
+    }
+
+    pub fn symbol(name: impl Into<String>) -> Self {
+        Value::Symbol(name.into())
+    }
+
+    /// `(elems . tail)`, a tail that is a list is appended to `elems`.
+    pub fn dotted(mut elems: Vec<Value>, tail: Value) -> Self {
+        match tail {
+            Value::List(rest) => {
+                elems.extend(rest);
+                Value::List(elems)
+            }
+            Value::DottedList(rest, tail) => {
+                elems.extend(rest);
+                Value::DottedList(elems, tail)
+            }
+            tail => Value::DottedList(elems, Box::new(tail)),
+        }
+    }
+
+    /// An exact number, an integer if it is one.
+    fn exact(value: BigRational) -> Self {
+        if value.is_integer() {
+            Value::Int(value.to_integer())
+        } else {
+            Value::Rational(value)
+        }
+    }
+
+    fn float(value: f64, exactness: Option<Exactness>) -> Self {
+        match exactness {
+            Some(Exactness::Exact) => match BigRational::from_f64(value) {
+                Some(value) => Value::exact(value),
+                None => Value::Float(value),
+            },
+            _ => Value::Float(value),
+        }
+    }
+}
+
+impl TryFrom<&ENotation> for Value {
+    /// The span of an error node of the recovering parser, which has no value, or of a
+    /// label reference that cannot be copied.
+    type Error = DiagnosticSpan;
+
+    /// Shared structure is copied, `(#0=(a) #0#)` is `((a) (a))`, and a cycle fails at the
+    /// reference that closes it, use a [`Graph`](crate::labeling::Graph) to keep it.
+    fn try_from(notation: &ENotation) -> Result<Self, DiagnosticSpan> {
+        Labels::default().value(notation)
+    }
+}
+
+/// The values of the labels read so far, `None` while a label's own value is read.
+#[derive(Default)]
+struct Labels(HashMap<u64, Option<Value>>);
+
+impl Labels {
+    fn value(&mut self, notation: &ENotation) -> Result<Value, DiagnosticSpan> {
+        let mut all = |elems: &[ENotation]| {
+            elems
+                .iter()
+                .map(|elem| self.value(elem))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(match &notation.body {
+            ENotationBody::Literal(literal) => match literal {
+                Literal::Boolean(b) => Value::Bool(b.value),
+                Literal::Char(c) => Value::Char(c.value),
+                Literal::Int(i) if i.exactness == Some(Exactness::Inexact) => {
+                    Value::Float(i.to_f64())
+                }
+                Literal::Int(i) => Value::Int(i.as_bigint()),
+                Literal::Rational(r) if r.exactness == Some(Exactness::Inexact) => {
+                    Value::Float(r.to_f64())
+                }
+                Literal::Rational(r) => Value::exact(r.value.clone()),
+                Literal::Float(f) => Value::float(f.value, f.exactness),
+                Literal::Complex(c) => Value::Complex(c.to_complex64()),
+                Literal::String_(s) => Value::String(s.value.clone()),
+                Literal::Identifier(i) => Value::Symbol(i.symbol().to_string()),
+                Literal::Keyword(k) => Value::Keyword(k.name.clone()),
+                Literal::ByteString(b) => Value::Bytes(b.value.clone()),
+            },
+            ENotationBody::Container(container) => match container {
+                Container::List(l) => {
+                    let elems = all(l.elems())?;
+                    match l.tail() {
+                        Some(tail) => Value::dotted(elems, self.value(tail)?),
+                        None => Value::List(elems),
+                    }
+                }
+                Container::Vector(v) => Value::Vector(all(v.elems())?),
+                Container::Set(s) => Value::Set(all(&s.elems)?),
+                Container::Object(o) => Value::Map(
+                    o.pairs
+                        .iter()
+                        .map(|pair| Ok((pair.key.symbol().to_string(), self.value(&pair.value)?)))
+                        .collect::<Result<_, _>>()?,
+                ),
+                Container::Bytevector(b) => Value::Bytes(b.bytes.clone()),
+                Container::Hash(h) => Value::Hash(
+                    h.flavor,
+                    h.entries
+                        .iter()
+                        .map(|entry| Ok((self.value(&entry.key)?, self.value(&entry.value)?)))
+                        .collect::<Result<_, _>>()?,
+                ),
+            },
+            ENotationBody::Quoting(q) => {
+                let (kind, value) = QuoteKind::of_quoting(q);
+                Value::Quoted(kind, Box::new(self.value(value)?))
+            }
+            ENotationBody::Syntaxing(s) => {
+                let (kind, value) = QuoteKind::of_syntaxing(s);
+                Value::Quoted(kind, Box::new(self.value(value)?))
+            }
+            ENotationBody::Labeling(Labeling::Label(label)) => {
+                self.0.insert(label.label, None);
+                let value = self.value(&label.value)?;
+                self.0.insert(label.label, Some(value.clone()));
+                value
+            }
+            ENotationBody::Labeling(Labeling::LabelRef(label_ref)) => {
+                match self.0.get(&label_ref.label) {
+                    Some(Some(value)) => value.clone(),
+                    _ => return Err(notation.span.clone()),
+                }
+            }
+            ENotationBody::Error(_) => return Err(notation.span.clone()),
+        })
+    }
+}
+
+/// A name no notation reads back as, so a value holding it cannot be written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WriteError {
+    /// A symbol or object key that needs `|` quotes but contains a `|`.
+    Symbol(String),
+    /// A keyword without a name, `#:` alone is not a keyword.
+    EmptyKeyword,
+}
+
+impl Display for WriteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WriteError::Symbol(symbol) => write!(f, "`{}` cannot be written as a symbol", symbol),
+            WriteError::EmptyKeyword => write!(f, "a keyword needs a name"),
+        }
+    }
+}
+
+impl std::error::Error for WriteError {}
+
+pub(crate) fn identifier(symbol: &str) -> Result<Identifier, WriteError> {
+    Identifier::from_symbol(symbol).ok_or_else(|| WriteError::Symbol(symbol.to_string()))
+}
+
+fn keyword(name: String) -> Result<Keyword, WriteError> {
+    match name.is_empty() {
+        true => Err(WriteError::EmptyKeyword),
+        false => Ok(Keyword { name }),
+    }
+}
+
+fn float(value: f64) -> Float {
+    Float {
+        value,
+        exactness: None,
+    }
+}
+
+/// Builds a notation with empty spans, lists use parentheses, vectors `#(` and bytes `#u8(`.
+impl TryFrom<Value> for ENotation {
+    type Error = WriteError;
+
+    fn try_from(value: Value) -> Result<Self, WriteError> {
+        let all = |elems: Vec<Value>| {
+            elems
+                .into_iter()
+                .map(ENotation::try_from)
+                .collect::<Result<_, _>>()
+        };
+        let body = match value {
+            Value::Bool(value) => ENotationBody::Literal(Literal::Boolean(Boolean { value })),
+            Value::Char(value) => ENotationBody::Literal(Literal::Char(Char { value })),
+            Value::Int(value) => ENotationBody::Literal(Literal::Int(Integer {
+                value: match i64::try_from(&value) {
+                    Ok(value) => IntegerValue::Small(value),
+                    Err(_) => IntegerValue::Big(value),
+                },
+                radix: Default::default(),
+                exactness: None,
+            })),
+            Value::Rational(value) => ENotationBody::Literal(Literal::Rational(Rational {
+                value,
+                radix: Default::default(),
+                exactness: None,
+            })),
+            Value::Float(value) => ENotationBody::Literal(Literal::Float(float(value))),
+            Value::Complex(value) => ENotationBody::Literal(Literal::Complex(Complex {
+                value: ComplexValue::Rectangular(num::Complex::new(
+                    Real::Float(float(value.re)),
+                    Real::Float(float(value.im)),
+                )),
+                exactness: None,
+            })),
+            Value::String(value) => ENotationBody::Literal(Literal::String_(String_ { value })),
+            Value::Symbol(symbol) => {
+                ENotationBody::Literal(Literal::Identifier(identifier(&symbol)?))
+            }
+            Value::Keyword(name) => ENotationBody::Literal(Literal::Keyword(keyword(name)?)),
+            Value::Bytes(bytes) => {
+                ENotationBody::Container(Container::Bytevector(Bytevector { bytes }))
+            }
+            Value::List(elems) => ENotationBody::Container(Container::List(List::PL(PList {
+                elems: all(elems)?,
+                tail: None,
+            }))),
+            Value::DottedList(elems, tail) => {
+                ENotationBody::Container(Container::List(List::PL(PList {
+                    elems: all(elems)?,
+                    tail: Some(Box::new(ENotation::try_from(*tail)?)),
+                })))
+            }
+            Value::Vector(elems) => {
+                ENotationBody::Container(Container::Vector(Vector::PV(PVector {
+                    elems: all(elems)?,
+                })))
+            }
+            Value::Set(elems) => {
+                ENotationBody::Container(Container::Set(Set { elems: all(elems)? }))
+            }
+            Value::Map(pairs) => ENotationBody::Container(Container::Object(Object {
+                pairs: pairs
+                    .into_iter()
+                    .map(|(key, value)| {
+                        Ok(ObjectPair {
+                            key: identifier(&key)?,
+                            value: value.try_into()?,
+                        })
+                    })
+                    .collect::<Result<_, WriteError>>()?,
+            })),
+            Value::Hash(flavor, entries) => ENotationBody::Container(Container::Hash(HashTable {
+                flavor,
+                entries: entries
+                    .into_iter()
+                    .map(|(key, value)| {
+                        Ok(HashEntry {
+                            key: key.try_into()?,
+                            value: value.try_into()?,
+                        })
+                    })
+                    .collect::<Result<_, WriteError>>()?,
+            })),
+            Value::Quoted(kind, value) => kind.wrap(Box::new(ENotation::try_from(*value)?)),
+        };
+        Ok(ENotation {
+            span: DiagnosticSpan::default(),
+            body,
+        })
+    }
+}
+
+impl FromStr for Value {
+    type Err = ParseError;
+
+    /// Parses exactly one notation, which never contains error nodes, but may be cyclic.
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        (&parse_notation(input)?)
+            .try_into()
+            .map_err(|span| ParseError {
+                kind: ParseErrorKind::CyclicValue,
+                span,
+                expected: vec![],
+                related: None,
+            })
+    }
+}
+
+/// Fails for a value that cannot be written, see [`WriteError`].
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let notation = ENotation::try_from(self.clone()).map_err(|_| std::fmt::Error)?;
+        write!(f, "{}", notation)
+    }
+}
@@ -0,0 +1,123 @@
+use insta::assert_snapshot;
+
+use super::{QuoteKind, Value};
+use crate::{parse_file_recovering, ENotation};
+
+fn value(input: &str) -> Value {
+    input.parse().unwrap()
+}
+
+#[test]
+fn from_notation() {
+    assert_eq!(value("[a |b c|]"), value("(a |b c|)"));
+    assert_eq!(
+        value("{k : '(1 #e1.5 4/2 #i1)}"),
+        Value::Map(vec![(
+            "k".to_string(),
+            Value::Quoted(
+                QuoteKind::Quote,
+                Box::new(Value::List(vec![
+                    Value::Int(1.into()),
+                    Value::Rational(num::BigRational::new(3.into(), 2.into())),
+                    Value::Int(2.into()),
+                    Value::Float(1.0),
+                ]))
+            )
+        )])
+    );
+}
+
+#[test]
+fn to_notation() {
+    let values = [
+        value(r#"[#t #\a #x-ff 1/3 #e0.25 1.0 1+2i "s" |a b| #[x] #{y} {z : ()}]"#),
+        value("(#'a #`b #,c #,@d 'e `f ,g ,@h)"),
+        Value::symbol("12"),
+    ];
+    let printed: Vec<_> = values.iter().map(|v| v.to_string()).collect();
+    assert_snapshot!(printed.join("\n"), @r#"
+    (#t #\a -255 1/3 1/4 1.0 1.0+2.0i "s" |a b| #(x) #{y} {z : ()})
+    (#'a #`b #,c #,@d 'e `f ,g ,@h)
+    |12|
+    "#);
+    for v in &values {
+        assert_eq!(&value(&v.to_string()), v);
+    }
+}
+
+#[test]
+fn unwritable_names() {
+    let unwritable = [
+        Value::symbol("x|y"),
+        Value::List(vec![Value::Keyword(String::new())]),
+        Value::Map(vec![("a|b".to_string(), Value::nil())]),
+    ];
+    let errors: Vec<_> = unwritable
+        .into_iter()
+        .map(|v| ENotation::try_from(v).unwrap_err().to_string())
+        .collect();
+    assert_snapshot!(errors.join("\n"), @r"
+    `x|y` cannot be written as a symbol
+    a keyword needs a name
+    `a|b` cannot be written as a symbol
+    ");
+}
+
+#[test]
+fn dotted_tails_continue_lists() {
+    assert_eq!(value("(a . (b c))"), value("(a b c)"));
+    assert_eq!(value("(a . ())"), value("(a)"));
+    assert_eq!(
+        value("[a . (b . c)]"),
+        Value::DottedList(
+            vec![Value::symbol("a"), Value::symbol("b")],
+            Box::new(Value::symbol("c"))
+        )
+    );
+    assert_snapshot!(value("(a . (b . c))"), @"(a b . c)");
+}
+
+#[test]
+fn exact_floats_must_be_finite() {
+    assert_eq!(value("#e1e2"), Value::Int(100.into()));
+    let errors: Vec<_> = ["#e1e400", "#e+inf.0", "#E-nan.0"]
+        .iter()
+        .map(|input| input.parse::<Value>().unwrap_err().to_string())
+        .collect();
+    assert_snapshot!(errors.join("\n"), @r"
+    1:1: invalid literal: `#e1e400` has no exact value
+    1:1: invalid literal: `#e+inf.0` has no exact value
+    1:1: invalid literal: `#E-nan.0` has no exact value
+    ");
+}
+
+#[test]
+fn hash_tables() {
+    let v = value("#hasheqv((1 . #e1.5) (\"s\" . (a . b)))");
+    assert_eq!(
+        v,
+        Value::Hash(
+            crate::container::hash::HashFlavor::Eqv,
+            vec![
+                (
+                    Value::Int(1.into()),
+                    Value::Rational(num::BigRational::new(3.into(), 2.into()))
+                ),
+                (
+                    Value::String("s".to_string()),
+                    Value::dotted(vec![Value::symbol("a")], Value::symbol("b"))
+                ),
+            ]
+        )
+    );
+    assert_snapshot!(v, @r#"#hasheqv((1 . 3/2) ("s" . (a . b)))"#);
+}
+
+#[test]
+fn error_nodes_have_no_value() {
+    let (file, _) = parse_file_recovering("(a) (b");
+    let values: Vec<_> = file.notations.iter().map(Value::try_from).collect();
+    assert_eq!(values[0], Ok(value("(a)")));
+    assert_eq!(values[1].as_ref().unwrap_err().span, "(b");
+    assert_snapshot!(ENotation::try_from(Value::nil()).unwrap(), @"()");
+}
@@ -0,0 +1,424 @@
+//! Walks a tree without changing it.
+//!
+//! Every method of [`Visit`] visits the children of its node through the function of the same
+//! name in this module, a pass overrides the methods of the nodes it looks at and calls that
+//! function to keep walking below them:
+//!
+//! ```
+//! use enotation::{literal::Identifier, parse_notation, visit::{self, Visit}};
+//!
+//! struct Symbols(Vec<String>);
+//!
+//! impl Visit for Symbols {
+//!     fn visit_identifier(&mut self, node: &Identifier) {
+//!         self.0.push(node.symbol().to_string());
+//!         visit::visit_identifier(self, node);
+//!     }
+//! }
+//!
+//! let mut symbols = Symbols(vec![]);
+//! symbols.visit_notation(&parse_notation("(define x {y : '|z|})").unwrap());
+//! assert_eq!(symbols.0, ["define", "x", "y", "z"]);
+//! ```
+
+use crate::{
+    container::{
+        bytevector::Bytevector,
+        hash::{HashEntry, HashTable},
+        list::{BList, List, PList},
+        object::{Object, ObjectPair},
+        set::Set,
+        vector::{BVector, PVector, Vector},
+        Container,
+    },
+    labeling::{Label, LabelRef, Labeling},
+    literal::{
+        Boolean, ByteString, Char, Complex, Float, Identifier, Integer, Keyword, Literal, Rational,
+        String_,
+    },
+    quoting::{QuasiQuote, Quote, Quoting, Unquote, UnquoteSplicing},
+    syntaxing::{QuasiSyntax, Syntax, Syntaxing, Unsyntax, UnsyntaxSplicing},
+    DiagnosticSpan, EFile, ENotation, ENotationBody, ErrorNode,
+};
+
+#[cfg(test)]
+mod tests;
+
+pub trait Visit {
+    fn visit_file(&mut self, node: &EFile) {
+        visit_file(self, node)
+    }
+
+    fn visit_notation(&mut self, node: &ENotation) {
+        visit_notation(self, node)
+    }
+
+    fn visit_span(&mut self, node: &DiagnosticSpan) {
+        visit_span(self, node)
+    }
+
+    fn visit_body(&mut self, node: &ENotationBody) {
+        visit_body(self, node)
+    }
+
+    fn visit_error_node(&mut self, node: &ErrorNode) {
+        visit_error_node(self, node)
+    }
+
+    fn visit_literal(&mut self, node: &Literal) {
+        visit_literal(self, node)
+    }
+
+    fn visit_boolean(&mut self, node: &Boolean) {
+        visit_boolean(self, node)
+    }
+
+    fn visit_char(&mut self, node: &Char) {
+        visit_char(self, node)
+    }
+
+    fn visit_float(&mut self, node: &Float) {
+        visit_float(self, node)
+    }
+
+    fn visit_rational(&mut self, node: &Rational) {
+        visit_rational(self, node)
+    }
+
+    fn visit_integer(&mut self, node: &Integer) {
+        visit_integer(self, node)
+    }
+
+    fn visit_complex(&mut self, node: &Complex) {
+        visit_complex(self, node)
+    }
+
+    fn visit_string(&mut self, node: &String_) {
+        visit_string(self, node)
+    }
+
+    fn visit_identifier(&mut self, node: &Identifier) {
+        visit_identifier(self, node)
+    }
+
+    fn visit_keyword(&mut self, node: &Keyword) {
+        visit_keyword(self, node)
+    }
+
+    fn visit_byte_string(&mut self, node: &ByteString) {
+        visit_byte_string(self, node)
+    }
+
+    fn visit_container(&mut self, node: &Container) {
+        visit_container(self, node)
+    }
+
+    fn visit_list(&mut self, node: &List) {
+        visit_list(self, node)
+    }
+
+    fn visit_plist(&mut self, node: &PList) {
+        visit_plist(self, node)
+    }
+
+    fn visit_blist(&mut self, node: &BList) {
+        visit_blist(self, node)
+    }
+
+    fn visit_vector(&mut self, node: &Vector) {
+        visit_vector(self, node)
+    }
+
+    fn visit_pvector(&mut self, node: &PVector) {
+        visit_pvector(self, node)
+    }
+
+    fn visit_bvector(&mut self, node: &BVector) {
+        visit_bvector(self, node)
+    }
+
+    fn visit_set(&mut self, node: &Set) {
+        visit_set(self, node)
+    }
+
+    fn visit_object(&mut self, node: &Object) {
+        visit_object(self, node)
+    }
+
+    fn visit_object_pair(&mut self, node: &ObjectPair) {
+        visit_object_pair(self, node)
+    }
+
+    fn visit_bytevector(&mut self, node: &Bytevector) {
+        visit_bytevector(self, node)
+    }
+
+    fn visit_hash_table(&mut self, node: &HashTable) {
+        visit_hash_table(self, node)
+    }
+
+    fn visit_hash_entry(&mut self, node: &HashEntry) {
+        visit_hash_entry(self, node)
+    }
+
+    fn visit_quoting(&mut self, node: &Quoting) {
+        visit_quoting(self, node)
+    }
+
+    fn visit_quote(&mut self, node: &Quote) {
+        visit_quote(self, node)
+    }
+
+    fn visit_quasi_quote(&mut self, node: &QuasiQuote) {
+        visit_quasi_quote(self, node)
+    }
+
+    fn visit_unquote(&mut self, node: &Unquote) {
+        visit_unquote(self, node)
+    }
+
+    fn visit_unquote_splicing(&mut self, node: &UnquoteSplicing) {
+        visit_unquote_splicing(self, node)
+    }
+
+    fn visit_syntaxing(&mut self, node: &Syntaxing) {
+        visit_syntaxing(self, node)
+    }
+
+    fn visit_syntax(&mut self, node: &Syntax) {
+        visit_syntax(self, node)
+    }
+
+    fn visit_quasi_syntax(&mut self, node: &QuasiSyntax) {
+        visit_quasi_syntax(self, node)
+    }
+
+    fn visit_unsyntax(&mut self, node: &Unsyntax) {
+        visit_unsyntax(self, node)
+    }
+
+    fn visit_unsyntax_splicing(&mut self, node: &UnsyntaxSplicing) {
+        visit_unsyntax_splicing(self, node)
+    }
+
+    fn visit_labeling(&mut self, node: &Labeling) {
+        visit_labeling(self, node)
+    }
+
+    fn visit_label(&mut self, node: &Label) {
+        visit_label(self, node)
+    }
+
+    fn visit_label_ref(&mut self, node: &LabelRef) {
+        visit_label_ref(self, node)
+    }
+}
+
+pub fn visit_file<V: Visit + ?Sized>(v: &mut V, node: &EFile) {
+    for elem in &node.notations {
+        v.visit_notation(elem);
+    }
+}
+
+pub fn visit_notation<V: Visit + ?Sized>(v: &mut V, node: &ENotation) {
+    v.visit_span(&node.span);
+    v.visit_body(&node.body);
+}
+
+pub fn visit_span<V: Visit + ?Sized>(_v: &mut V, _node: &DiagnosticSpan) {}
+
+pub fn visit_body<V: Visit + ?Sized>(v: &mut V, node: &ENotationBody) {
+    match node {
+        ENotationBody::Literal(x) => v.visit_literal(x),
+        ENotationBody::Container(x) => v.visit_container(x),
+        ENotationBody::Quoting(x) => v.visit_quoting(x),
+        ENotationBody::Syntaxing(x) => v.visit_syntaxing(x),
+        ENotationBody::Labeling(x) => v.visit_labeling(x),
+        ENotationBody::Error(x) => v.visit_error_node(x),
+    }
+}
+
+pub fn visit_error_node<V: Visit + ?Sized>(_v: &mut V, _node: &ErrorNode) {}
+
+pub fn visit_literal<V: Visit + ?Sized>(v: &mut V, node: &Literal) {
+    match node {
+        Literal::Boolean(x) => v.visit_boolean(x),
+        Literal::Char(x) => v.visit_char(x),
+        Literal::Float(x) => v.visit_float(x),
+        Literal::Rational(x) => v.visit_rational(x),
+        Literal::Int(x) => v.visit_integer(x),
+        Literal::Complex(x) => v.visit_complex(x),
+        Literal::String_(x) => v.visit_string(x),
+        Literal::Identifier(x) => v.visit_identifier(x),
+        Literal::Keyword(x) => v.visit_keyword(x),
+        Literal::ByteString(x) => v.visit_byte_string(x),
+    }
+}
+
+pub fn visit_boolean<V: Visit + ?Sized>(_v: &mut V, _node: &Boolean) {}
+
+pub fn visit_char<V: Visit + ?Sized>(_v: &mut V, _node: &Char) {}
+
+pub fn visit_float<V: Visit + ?Sized>(_v: &mut V, _node: &Float) {}
+
+pub fn visit_rational<V: Visit + ?Sized>(_v: &mut V, _node: &Rational) {}
+
+pub fn visit_integer<V: Visit + ?Sized>(_v: &mut V, _node: &Integer) {}
+
+pub fn visit_complex<V: Visit + ?Sized>(_v: &mut V, _node: &Complex) {}
+
+pub fn visit_string<V: Visit + ?Sized>(_v: &mut V, _node: &String_) {}
+
+pub fn visit_identifier<V: Visit + ?Sized>(_v: &mut V, _node: &Identifier) {}
+
+pub fn visit_keyword<V: Visit + ?Sized>(_v: &mut V, _node: &Keyword) {}
+
+pub fn visit_byte_string<V: Visit + ?Sized>(_v: &mut V, _node: &ByteString) {}
+
+pub fn visit_container<V: Visit + ?Sized>(v: &mut V, node: &Container) {
+    match node {
+        Container::List(x) => v.visit_list(x),
+        Container::Vector(x) => v.visit_vector(x),
+        Container::Set(x) => v.visit_set(x),
+        Container::Object(x) => v.visit_object(x),
+        Container::Bytevector(x) => v.visit_bytevector(x),
+        Container::Hash(x) => v.visit_hash_table(x),
+    }
+}
+
+pub fn visit_list<V: Visit + ?Sized>(v: &mut V, node: &List) {
+    match node {
+        List::PL(x) => v.visit_plist(x),
+        List::BL(x) => v.visit_blist(x),
+    }
+}
+
+pub fn visit_plist<V: Visit + ?Sized>(v: &mut V, node: &PList) {
+    for elem in &node.elems {
+        v.visit_notation(elem);
+    }
+    if let Some(tail) = &node.tail {
+        v.visit_notation(tail);
+    }
+}
+
+pub fn visit_blist<V: Visit + ?Sized>(v: &mut V, node: &BList) {
+    for elem in &node.elems {
+        v.visit_notation(elem);
+    }
+    if let Some(tail) = &node.tail {
+        v.visit_notation(tail);
+    }
+}
+
+pub fn visit_vector<V: Visit + ?Sized>(v: &mut V, node: &Vector) {
+    match node {
+        Vector::PV(x) => v.visit_pvector(x),
+        Vector::BV(x) => v.visit_bvector(x),
+    }
+}
+
+pub fn visit_pvector<V: Visit + ?Sized>(v: &mut V, node: &PVector) {
+    for elem in &node.elems {
+        v.visit_notation(elem);
+    }
+}
+
+pub fn visit_bvector<V: Visit + ?Sized>(v: &mut V, node: &BVector) {
+    for elem in &node.elems {
+        v.visit_notation(elem);
+    }
+}
+
+pub fn visit_set<V: Visit + ?Sized>(v: &mut V, node: &Set) {
+    for elem in &node.elems {
+        v.visit_notation(elem);
+    }
+}
+
+pub fn visit_object<V: Visit + ?Sized>(v: &mut V, node: &Object) {
+    for elem in &node.pairs {
+        v.visit_object_pair(elem);
+    }
+}
+
+pub fn visit_object_pair<V: Visit + ?Sized>(v: &mut V, node: &ObjectPair) {
+    v.visit_identifier(&node.key);
+    v.visit_notation(&node.value);
+}
+
+pub fn visit_bytevector<V: Visit + ?Sized>(_v: &mut V, _node: &Bytevector) {}
+
+pub fn visit_hash_table<V: Visit + ?Sized>(v: &mut V, node: &HashTable) {
+    for elem in &node.entries {
+        v.visit_hash_entry(elem);
+    }
+}
+
+pub fn visit_hash_entry<V: Visit + ?Sized>(v: &mut V, node: &HashEntry) {
+    v.visit_notation(&node.key);
+    v.visit_notation(&node.value);
+}
+
+pub fn visit_quoting<V: Visit + ?Sized>(v: &mut V, node: &Quoting) {
+    match node {
+        Quoting::Quote(x) => v.visit_quote(x),
+        Quoting::QuasiQuote(x) => v.visit_quasi_quote(x),
+        Quoting::Unquote(x) => v.visit_unquote(x),
+        Quoting::UnquoteSplicing(x) => v.visit_unquote_splicing(x),
+    }
+}
+
+pub fn visit_quote<V: Visit + ?Sized>(v: &mut V, node: &Quote) {
+    v.visit_notation(&node.value);
+}
+
+pub fn visit_quasi_quote<V: Visit + ?Sized>(v: &mut V, node: &QuasiQuote) {
+    v.visit_notation(&node.value);
+}
+
+pub fn visit_unquote<V: Visit + ?Sized>(v: &mut V, node: &Unquote) {
+    v.visit_notation(&node.value);
+}
+
+pub fn visit_unquote_splicing<V: Visit + ?Sized>(v: &mut V, node: &UnquoteSplicing) {
+    v.visit_notation(&node.value);
+}
+
+pub fn visit_syntaxing<V: Visit + ?Sized>(v: &mut V, node: &Syntaxing) {
+    match node {
+        Syntaxing::Syntax(x) => v.visit_syntax(x),
+        Syntaxing::QuasiSyntax(x) => v.visit_quasi_syntax(x),
+        Syntaxing::Unsyntax(x) => v.visit_unsyntax(x),
+        Syntaxing::UnsyntaxSplicing(x) => v.visit_unsyntax_splicing(x),
+    }
+}
+
+pub fn visit_syntax<V: Visit + ?Sized>(v: &mut V, node: &Syntax) {
+    v.visit_notation(&node.value);
+}
+
+pub fn visit_quasi_syntax<V: Visit + ?Sized>(v: &mut V, node: &QuasiSyntax) {
+    v.visit_notation(&node.value);
+}
+
+pub fn visit_unsyntax<V: Visit + ?Sized>(v: &mut V, node: &Unsyntax) {
+    v.visit_notation(&node.value);
+}
+
+pub fn visit_unsyntax_splicing<V: Visit + ?Sized>(v: &mut V, node: &UnsyntaxSplicing) {
+    v.visit_notation(&node.value);
+}
+
+pub fn visit_labeling<V: Visit + ?Sized>(v: &mut V, node: &Labeling) {
+    match node {
+        Labeling::Label(x) => v.visit_label(x),
+        Labeling::LabelRef(x) => v.visit_label_ref(x),
+    }
+}
+
+pub fn visit_label<V: Visit + ?Sized>(v: &mut V, node: &Label) {
+    v.visit_notation(&node.value);
+}
+
+pub fn visit_label_ref<V: Visit + ?Sized>(_v: &mut V, _node: &LabelRef) {}
@@ -0,0 +1,164 @@
+use insta::assert_snapshot;
+
+use crate::{
+    container::{
+        list::{List, PList},
+        Container,
+    },
+    fold::{self, Fold},
+    literal::{
+        Boolean, ByteString, Char, Complex, Float, Identifier, Integer, Keyword, Literal, Rational,
+        String_,
+    },
+    parse_file,
+    quoting::Quoting,
+    visit::{self, Visit},
+    visit_mut::{self, VisitMut},
+    ENotation, ENotationBody, SetDebugFileName,
+};
+
+#[derive(Default)]
+struct Depth {
+    current: usize,
+    max: usize,
+}
+
+impl Visit for Depth {
+    fn visit_container(&mut self, node: &Container) {
+        self.current += 1;
+        self.max = self.max.max(self.current);
+        visit::visit_container(self, node);
+        self.current -= 1;
+    }
+}
+
+#[test]
+fn visit_every_node() {
+    let file = parse_file("(a #(b [c {k : #{d}}])) 'e").unwrap();
+    let mut depth = Depth::default();
+    depth.visit_file(&file);
+    assert_eq!(depth.max, 5);
+}
+
+struct Rename;
+
+impl VisitMut for Rename {
+    fn visit_identifier_mut(&mut self, node: &mut Identifier) {
+        node.name = node.name.to_uppercase();
+    }
+}
+
+#[test]
+fn visit_mut_renames() {
+    let mut file = parse_file("(define x {key : `(y ,z)})").unwrap();
+    Rename.visit_file_mut(&mut file);
+    file.set_debug_file_name("a.esn");
+    assert_snapshot!(file, @"(DEFINE X {KEY : `(Y ,Z)})");
+    assert_eq!(file.notations[0].span.file.as_deref(), Some("a.esn"));
+}
+
+/// Expands `'x` to `(quote x)`.
+struct ExpandQuote;
+
+impl Fold for ExpandQuote {
+    fn fold_body(&mut self, node: ENotationBody) -> ENotationBody {
+        match fold::fold_body(self, node) {
+            ENotationBody::Quoting(Quoting::Quote(q)) => {
+                let quote = ENotation {
+                    span: q.value.span.clone(),
+                    body: ENotationBody::Literal(Literal::Identifier(Identifier {
+                        name: "quote".to_string(),
+                    })),
+                };
+                ENotationBody::Container(Container::List(List::PL(PList {
+                    elems: vec![quote, *q.value],
+                    tail: None,
+                })))
+            }
+            body => body,
+        }
+    }
+}
+
+#[test]
+fn fold_rewrites() {
+    let file = parse_file("'a (b ''c) `',d").unwrap();
+    assert_snapshot!(ExpandQuote.fold_file(file), @r"
+    (quote a)
+    (b (quote (quote c)))
+    `(quote ,d)
+    ");
+}
+
+#[test]
+fn walk_functions_recurse() {
+    struct Count(usize);
+    impl VisitMut for Count {
+        fn visit_notation_mut(&mut self, node: &mut ENotation) {
+            self.0 += 1;
+            visit_mut::visit_notation_mut(self, node);
+        }
+    }
+    let mut file = parse_file("(a (b c) #,d)").unwrap();
+    let mut count = Count(0);
+    count.visit_file_mut(&mut file);
+    assert_eq!(count.0, 7);
+}
+
+#[derive(Default)]
+struct Kinds(Vec<&'static str>);
+
+impl Visit for Kinds {
+    fn visit_boolean(&mut self, _node: &Boolean) {
+        self.0.push("boolean");
+    }
+    fn visit_char(&mut self, _node: &Char) {
+        self.0.push("char");
+    }
+    fn visit_float(&mut self, _node: &Float) {
+        self.0.push("float");
+    }
+    fn visit_rational(&mut self, _node: &Rational) {
+        self.0.push("rational");
+    }
+    fn visit_integer(&mut self, _node: &Integer) {
+        self.0.push("integer");
+    }
+    fn visit_complex(&mut self, _node: &Complex) {
+        self.0.push("complex");
+    }
+    fn visit_string(&mut self, _node: &String_) {
+        self.0.push("string");
+    }
+    fn visit_identifier(&mut self, _node: &Identifier) {
+        self.0.push("identifier");
+    }
+    fn visit_keyword(&mut self, _node: &Keyword) {
+        self.0.push("keyword");
+    }
+    fn visit_byte_string(&mut self, _node: &ByteString) {
+        self.0.push("byte string");
+    }
+}
+
+#[test]
+fn visit_every_literal() {
+    let file = parse_file(r#"(#t #\a 1.5 1/2 3 1+2i "s" x #:k #"b")"#).unwrap();
+    let mut kinds = Kinds::default();
+    kinds.visit_file(&file);
+    assert_eq!(
+        kinds.0,
+        [
+            "boolean",
+            "char",
+            "float",
+            "rational",
+            "integer",
+            "complex",
+            "string",
+            "identifier",
+            "keyword",
+            "byte string"
+        ]
+    );
+}
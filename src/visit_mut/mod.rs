@@ -0,0 +1,404 @@
+//! Walks a tree mutably, see [`Visit`](crate::visit::Visit) for the shared tree.
+//!
+//! Every method of [`VisitMut`] visits the children of its node through the function of the
+//! same name in this module, a pass overrides the methods of the nodes it changes and calls
+//! that function to keep walking below them.
+
+use crate::{
+    container::{
+        bytevector::Bytevector,
+        hash::{HashEntry, HashTable},
+        list::{BList, List, PList},
+        object::{Object, ObjectPair},
+        set::Set,
+        vector::{BVector, PVector, Vector},
+        Container,
+    },
+    labeling::{Label, LabelRef, Labeling},
+    literal::{
+        Boolean, ByteString, Char, Complex, Float, Identifier, Integer, Keyword, Literal, Rational,
+        String_,
+    },
+    quoting::{QuasiQuote, Quote, Quoting, Unquote, UnquoteSplicing},
+    syntaxing::{QuasiSyntax, Syntax, Syntaxing, Unsyntax, UnsyntaxSplicing},
+    DiagnosticSpan, EFile, ENotation, ENotationBody, ErrorNode,
+};
+
+pub trait VisitMut {
+    fn visit_file_mut(&mut self, node: &mut EFile) {
+        visit_file_mut(self, node)
+    }
+
+    fn visit_notation_mut(&mut self, node: &mut ENotation) {
+        visit_notation_mut(self, node)
+    }
+
+    fn visit_span_mut(&mut self, node: &mut DiagnosticSpan) {
+        visit_span_mut(self, node)
+    }
+
+    fn visit_body_mut(&mut self, node: &mut ENotationBody) {
+        visit_body_mut(self, node)
+    }
+
+    fn visit_error_node_mut(&mut self, node: &mut ErrorNode) {
+        visit_error_node_mut(self, node)
+    }
+
+    fn visit_literal_mut(&mut self, node: &mut Literal) {
+        visit_literal_mut(self, node)
+    }
+
+    fn visit_boolean_mut(&mut self, node: &mut Boolean) {
+        visit_boolean_mut(self, node)
+    }
+
+    fn visit_char_mut(&mut self, node: &mut Char) {
+        visit_char_mut(self, node)
+    }
+
+    fn visit_float_mut(&mut self, node: &mut Float) {
+        visit_float_mut(self, node)
+    }
+
+    fn visit_rational_mut(&mut self, node: &mut Rational) {
+        visit_rational_mut(self, node)
+    }
+
+    fn visit_integer_mut(&mut self, node: &mut Integer) {
+        visit_integer_mut(self, node)
+    }
+
+    fn visit_complex_mut(&mut self, node: &mut Complex) {
+        visit_complex_mut(self, node)
+    }
+
+    fn visit_string_mut(&mut self, node: &mut String_) {
+        visit_string_mut(self, node)
+    }
+
+    fn visit_identifier_mut(&mut self, node: &mut Identifier) {
+        visit_identifier_mut(self, node)
+    }
+
+    fn visit_keyword_mut(&mut self, node: &mut Keyword) {
+        visit_keyword_mut(self, node)
+    }
+
+    fn visit_byte_string_mut(&mut self, node: &mut ByteString) {
+        visit_byte_string_mut(self, node)
+    }
+
+    fn visit_container_mut(&mut self, node: &mut Container) {
+        visit_container_mut(self, node)
+    }
+
+    fn visit_list_mut(&mut self, node: &mut List) {
+        visit_list_mut(self, node)
+    }
+
+    fn visit_plist_mut(&mut self, node: &mut PList) {
+        visit_plist_mut(self, node)
+    }
+
+    fn visit_blist_mut(&mut self, node: &mut BList) {
+        visit_blist_mut(self, node)
+    }
+
+    fn visit_vector_mut(&mut self, node: &mut Vector) {
+        visit_vector_mut(self, node)
+    }
+
+    fn visit_pvector_mut(&mut self, node: &mut PVector) {
+        visit_pvector_mut(self, node)
+    }
+
+    fn visit_bvector_mut(&mut self, node: &mut BVector) {
+        visit_bvector_mut(self, node)
+    }
+
+    fn visit_set_mut(&mut self, node: &mut Set) {
+        visit_set_mut(self, node)
+    }
+
+    fn visit_object_mut(&mut self, node: &mut Object) {
+        visit_object_mut(self, node)
+    }
+
+    fn visit_object_pair_mut(&mut self, node: &mut ObjectPair) {
+        visit_object_pair_mut(self, node)
+    }
+
+    fn visit_bytevector_mut(&mut self, node: &mut Bytevector) {
+        visit_bytevector_mut(self, node)
+    }
+
+    fn visit_hash_table_mut(&mut self, node: &mut HashTable) {
+        visit_hash_table_mut(self, node)
+    }
+
+    fn visit_hash_entry_mut(&mut self, node: &mut HashEntry) {
+        visit_hash_entry_mut(self, node)
+    }
+
+    fn visit_quoting_mut(&mut self, node: &mut Quoting) {
+        visit_quoting_mut(self, node)
+    }
+
+    fn visit_quote_mut(&mut self, node: &mut Quote) {
+        visit_quote_mut(self, node)
+    }
+
+    fn visit_quasi_quote_mut(&mut self, node: &mut QuasiQuote) {
+        visit_quasi_quote_mut(self, node)
+    }
+
+    fn visit_unquote_mut(&mut self, node: &mut Unquote) {
+        visit_unquote_mut(self, node)
+    }
+
+    fn visit_unquote_splicing_mut(&mut self, node: &mut UnquoteSplicing) {
+        visit_unquote_splicing_mut(self, node)
+    }
+
+    fn visit_syntaxing_mut(&mut self, node: &mut Syntaxing) {
+        visit_syntaxing_mut(self, node)
+    }
+
+    fn visit_syntax_mut(&mut self, node: &mut Syntax) {
+        visit_syntax_mut(self, node)
+    }
+
+    fn visit_quasi_syntax_mut(&mut self, node: &mut QuasiSyntax) {
+        visit_quasi_syntax_mut(self, node)
+    }
+
+    fn visit_unsyntax_mut(&mut self, node: &mut Unsyntax) {
+        visit_unsyntax_mut(self, node)
+    }
+
+    fn visit_unsyntax_splicing_mut(&mut self, node: &mut UnsyntaxSplicing) {
+        visit_unsyntax_splicing_mut(self, node)
+    }
+
+    fn visit_labeling_mut(&mut self, node: &mut Labeling) {
+        visit_labeling_mut(self, node)
+    }
+
+    fn visit_label_mut(&mut self, node: &mut Label) {
+        visit_label_mut(self, node)
+    }
+
+    fn visit_label_ref_mut(&mut self, node: &mut LabelRef) {
+        visit_label_ref_mut(self, node)
+    }
+}
+
+pub fn visit_file_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut EFile) {
+    for elem in &mut node.notations {
+        v.visit_notation_mut(elem);
+    }
+}
+
+pub fn visit_notation_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ENotation) {
+    v.visit_span_mut(&mut node.span);
+    v.visit_body_mut(&mut node.body);
+}
+
+pub fn visit_span_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut DiagnosticSpan) {}
+
+pub fn visit_body_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ENotationBody) {
+    match node {
+        ENotationBody::Literal(x) => v.visit_literal_mut(x),
+        ENotationBody::Container(x) => v.visit_container_mut(x),
+        ENotationBody::Quoting(x) => v.visit_quoting_mut(x),
+        ENotationBody::Syntaxing(x) => v.visit_syntaxing_mut(x),
+        ENotationBody::Labeling(x) => v.visit_labeling_mut(x),
+        ENotationBody::Error(x) => v.visit_error_node_mut(x),
+    }
+}
+
+pub fn visit_error_node_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut ErrorNode) {}
+
+pub fn visit_literal_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Literal) {
+    match node {
+        Literal::Boolean(x) => v.visit_boolean_mut(x),
+        Literal::Char(x) => v.visit_char_mut(x),
+        Literal::Float(x) => v.visit_float_mut(x),
+        Literal::Rational(x) => v.visit_rational_mut(x),
+        Literal::Int(x) => v.visit_integer_mut(x),
+        Literal::Complex(x) => v.visit_complex_mut(x),
+        Literal::String_(x) => v.visit_string_mut(x),
+        Literal::Identifier(x) => v.visit_identifier_mut(x),
+        Literal::Keyword(x) => v.visit_keyword_mut(x),
+        Literal::ByteString(x) => v.visit_byte_string_mut(x),
+    }
+}
+
+pub fn visit_boolean_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut Boolean) {}
+
+pub fn visit_char_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut Char) {}
+
+pub fn visit_float_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut Float) {}
+
+pub fn visit_rational_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut Rational) {}
+
+pub fn visit_integer_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut Integer) {}
+
+pub fn visit_complex_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut Complex) {}
+
+pub fn visit_string_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut String_) {}
+
+pub fn visit_identifier_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut Identifier) {}
+
+pub fn visit_keyword_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut Keyword) {}
+
+pub fn visit_byte_string_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut ByteString) {}
+
+pub fn visit_container_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Container) {
+    match node {
+        Container::List(x) => v.visit_list_mut(x),
+        Container::Vector(x) => v.visit_vector_mut(x),
+        Container::Set(x) => v.visit_set_mut(x),
+        Container::Object(x) => v.visit_object_mut(x),
+        Container::Bytevector(x) => v.visit_bytevector_mut(x),
+        Container::Hash(x) => v.visit_hash_table_mut(x),
+    }
+}
+
+pub fn visit_list_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut List) {
+    match node {
+        List::PL(x) => v.visit_plist_mut(x),
+        List::BL(x) => v.visit_blist_mut(x),
+    }
+}
+
+pub fn visit_plist_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PList) {
+    for elem in &mut node.elems {
+        v.visit_notation_mut(elem);
+    }
+    if let Some(tail) = &mut node.tail {
+        v.visit_notation_mut(tail);
+    }
+}
+
+pub fn visit_blist_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut BList) {
+    for elem in &mut node.elems {
+        v.visit_notation_mut(elem);
+    }
+    if let Some(tail) = &mut node.tail {
+        v.visit_notation_mut(tail);
+    }
+}
+
+pub fn visit_vector_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Vector) {
+    match node {
+        Vector::PV(x) => v.visit_pvector_mut(x),
+        Vector::BV(x) => v.visit_bvector_mut(x),
+    }
+}
+
+pub fn visit_pvector_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PVector) {
+    for elem in &mut node.elems {
+        v.visit_notation_mut(elem);
+    }
+}
+
+pub fn visit_bvector_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut BVector) {
+    for elem in &mut node.elems {
+        v.visit_notation_mut(elem);
+    }
+}
+
+pub fn visit_set_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Set) {
+    for elem in &mut node.elems {
+        v.visit_notation_mut(elem);
+    }
+}
+
+pub fn visit_object_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Object) {
+    for elem in &mut node.pairs {
+        v.visit_object_pair_mut(elem);
+    }
+}
+
+pub fn visit_object_pair_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ObjectPair) {
+    v.visit_identifier_mut(&mut node.key);
+    v.visit_notation_mut(&mut node.value);
+}
+
+pub fn visit_bytevector_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut Bytevector) {}
+
+pub fn visit_hash_table_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut HashTable) {
+    for elem in &mut node.entries {
+        v.visit_hash_entry_mut(elem);
+    }
+}
+
+pub fn visit_hash_entry_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut HashEntry) {
+    v.visit_notation_mut(&mut node.key);
+    v.visit_notation_mut(&mut node.value);
+}
+
+pub fn visit_quoting_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Quoting) {
+    match node {
+        Quoting::Quote(x) => v.visit_quote_mut(x),
+        Quoting::QuasiQuote(x) => v.visit_quasi_quote_mut(x),
+        Quoting::Unquote(x) => v.visit_unquote_mut(x),
+        Quoting::UnquoteSplicing(x) => v.visit_unquote_splicing_mut(x),
+    }
+}
+
+pub fn visit_quote_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Quote) {
+    v.visit_notation_mut(&mut node.value);
+}
+
+pub fn visit_quasi_quote_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut QuasiQuote) {
+    v.visit_notation_mut(&mut node.value);
+}
+
+pub fn visit_unquote_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Unquote) {
+    v.visit_notation_mut(&mut node.value);
+}
+
+pub fn visit_unquote_splicing_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut UnquoteSplicing) {
+    v.visit_notation_mut(&mut node.value);
+}
+
+pub fn visit_syntaxing_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Syntaxing) {
+    match node {
+        Syntaxing::Syntax(x) => v.visit_syntax_mut(x),
+        Syntaxing::QuasiSyntax(x) => v.visit_quasi_syntax_mut(x),
+        Syntaxing::Unsyntax(x) => v.visit_unsyntax_mut(x),
+        Syntaxing::UnsyntaxSplicing(x) => v.visit_unsyntax_splicing_mut(x),
+    }
+}
+
+pub fn visit_syntax_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Syntax) {
+    v.visit_notation_mut(&mut node.value);
+}
+
+pub fn visit_quasi_syntax_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut QuasiSyntax) {
+    v.visit_notation_mut(&mut node.value);
+}
+
+pub fn visit_unsyntax_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Unsyntax) {
+    v.visit_notation_mut(&mut node.value);
+}
+
+pub fn visit_unsyntax_splicing_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut UnsyntaxSplicing) {
+    v.visit_notation_mut(&mut node.value);
+}
+
+pub fn visit_labeling_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Labeling) {
+    match node {
+        Labeling::Label(x) => v.visit_label_mut(x),
+        Labeling::LabelRef(x) => v.visit_label_ref_mut(x),
+    }
+}
+
+pub fn visit_label_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Label) {
+    v.visit_notation_mut(&mut node.value);
+}
+
+pub fn visit_label_ref_mut<V: VisitMut + ?Sized>(_v: &mut V, _node: &mut LabelRef) {}